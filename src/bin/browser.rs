@@ -0,0 +1,242 @@
+// The state of the interactive browser and how keys change it, kept apart from
+// the terminal so it can be tested without one
+
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crossterm::event::KeyCode;
+use lrg::TreeNode;
+
+// The orders children can be listed in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sort {
+    Size,
+    Name,
+    Modified,
+}
+
+impl Sort {
+    pub fn name(self) -> &'static str {
+        match self {
+            Sort::Size => "size",
+            Sort::Name => "name",
+            Sort::Modified => "mtime",
+        }
+    }
+
+    // Largest, first alphabetically or newest first, then by path
+    fn compare(self, a: &TreeNode, b: &TreeNode) -> Ordering {
+        let ordering = match self {
+            Sort::Size => b.size().cmp(&a.size()),
+            Sort::Name => a.name().cmp(b.name()),
+            Sort::Modified => {
+                let modified = |node: &TreeNode| node.entry().and_then(|entry| entry.modified());
+                // Nodes without a time go last
+                match (modified(a), modified(b)) {
+                    (Some(a), Some(b)) => b.cmp(&a),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                }
+            }
+        };
+        ordering.then_with(|| a.path().cmp(b.path()))
+    }
+}
+
+// The state of the browser
+pub struct Browser {
+    pub tree: TreeNode,
+    // The directory being listed
+    pub current: PathBuf,
+    pub selected: usize,
+    // The first child shown, once the list is longer than the screen
+    pub scroll: usize,
+    pub sort: Sort,
+    pub show_hidden: bool,
+    pub marked: BTreeSet<PathBuf>,
+    // The entries waiting to be confirmed for deletion
+    pub pending: Vec<PathBuf>,
+    pub message: Option<String>,
+}
+
+impl Browser {
+    // Starts browsing the root of the tree, largest first
+    pub fn new(tree: TreeNode) -> Browser {
+        Browser {
+            current: tree.path().to_path_buf(),
+            tree,
+            selected: 0,
+            scroll: 0,
+            sort: Sort::Size,
+            show_hidden: true,
+            marked: BTreeSet::new(),
+            pending: Vec::new(),
+            message: None,
+        }
+    }
+
+    // Gets the directory being listed, or the root if it was deleted
+    pub fn current(&self) -> &TreeNode {
+        self.tree.find(&self.current).unwrap_or(&self.tree)
+    }
+
+    // Gets the children of the directory being listed, in the order shown
+    pub fn children(&self) -> Vec<&TreeNode> {
+        let mut children: Vec<&TreeNode> = self
+            .current()
+            .children()
+            .iter()
+            .filter(|child| self.show_hidden || !child.name().to_string_lossy().starts_with('.'))
+            .collect();
+        children.sort_by(|a, b| self.sort.compare(a, b));
+        children
+    }
+
+    // Gets the path of the selected child, if there are any
+    pub fn selected(&self) -> Option<PathBuf> {
+        self.children().get(self.selected).map(|child| child.path().to_path_buf())
+    }
+
+    // Gets the total size of the entries waiting to be confirmed for deletion
+    pub fn pending_size(&self) -> u64 {
+        self.pending.iter().filter_map(|path| self.tree.find(path)).map(|node| node.size()).sum()
+    }
+
+    // Selects the child with the path, or the first if it isn't listed
+    fn select(&mut self, path: &Path) {
+        self.selected = self.children().iter().position(|child| child.path() == path).unwrap_or(0);
+    }
+
+    // Moves the selection, keeping it within the children
+    fn move_by(&mut self, offset: isize) {
+        let last = self.children().len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(offset).min(last);
+    }
+
+    // Handles a key, paging by the number of rows shown, returning whether to quit
+    pub fn handle(&mut self, key: KeyCode, page: isize) -> bool {
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            match key {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.delete(pending),
+                _ => self.message = Some(String::from("nothing deleted")),
+            }
+            return false;
+        }
+
+        self.message = None;
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_by(1),
+            KeyCode::PageUp => self.move_by(-page),
+            KeyCode::PageDown => self.move_by(page),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => self.move_by(isize::MAX),
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char('l') => {
+                let dir = self.children().get(self.selected).filter(|child| child.is_dir()).map(|child| child.path().to_path_buf());
+                if let Some(dir) = dir {
+                    self.current = dir;
+                    self.selected = 0;
+                    self.scroll = 0;
+                }
+            }
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') if self.current != self.tree.path() => {
+                let previous = self.current.clone();
+                self.current = previous.parent().map_or_else(|| self.tree.path().to_path_buf(), Path::to_path_buf);
+                self.scroll = 0;
+                self.select(&previous);
+            }
+            KeyCode::Char('s') => self.sort_by(Sort::Size),
+            KeyCode::Char('n') => self.sort_by(Sort::Name),
+            KeyCode::Char('m') => self.sort_by(Sort::Modified),
+            KeyCode::Char('.') => {
+                let selected = self.selected();
+                self.show_hidden = !self.show_hidden;
+                if let Some(selected) = selected {
+                    self.select(&selected);
+                }
+            }
+            KeyCode::Char(' ') => {
+                if let Some(selected) = self.selected() {
+                    if !self.marked.remove(&selected) {
+                        self.marked.insert(selected);
+                    }
+                    self.move_by(1);
+                }
+            }
+            KeyCode::Char('d') => {
+                // Delete the selected entry if none were marked
+                self.pending = if self.marked.is_empty() {
+                    self.selected().into_iter().collect()
+                } else {
+                    self.marked.iter().cloned().collect()
+                };
+            }
+            _ => {}
+        }
+        false
+    }
+
+    // Sorts the children, keeping the same child selected
+    fn sort_by(&mut self, sort: Sort) {
+        let selected = self.selected();
+        self.sort = sort;
+        if let Some(selected) = selected {
+            self.select(&selected);
+        }
+    }
+
+    // Deletes the entries from disk and from the tree
+    fn delete(&mut self, paths: Vec<PathBuf>) {
+        let mut deleted = 0;
+        let mut errors: Vec<String> = Vec::new();
+        for path in &paths {
+            self.marked.remove(path);
+            // Entries inside of a deleted directory are deleted along with it
+            if paths.iter().any(|other| other != path && path.starts_with(other)) {
+                continue;
+            }
+            match self.delete_scanned(path) {
+                Ok(()) => deleted += 1,
+                Err(err) if err.kind() == io::ErrorKind::DirectoryNotEmpty => {
+                    errors.push(format!("{}: kept what wasn't scanned beneath it", path.display()))
+                }
+                Err(err) => errors.push(format!("{}: {}", path.display(), err)),
+            }
+        }
+
+        self.message = Some(match errors.first() {
+            None => format!("deleted {} entries", deleted),
+            Some(error) => format!("deleted {} entries, couldn't delete {} ({})", deleted, errors.len(), error),
+        });
+        // The directory being listed may have been deleted
+        if self.tree.find(&self.current).is_none() {
+            self.current = self.tree.path().to_path_buf();
+        }
+        self.move_by(0);
+    }
+
+    // Deletes an entry and everything scanned beneath it, from disk and from the
+    // tree. Directories are only removed once they're empty, so whatever wasn't
+    // scanned (such as excluded or ignored files, or other file systems) is kept
+    fn delete_scanned(&mut self, path: &Path) -> io::Result<()> {
+        let children: Vec<PathBuf> = match self.tree.find(path) {
+            Some(node) if node.is_dir() => node.children().iter().map(|child| child.path().to_path_buf()).collect(),
+            _ => Vec::new(),
+        };
+        // Links are removed themselves, even if they were followed
+        if fs::symlink_metadata(path)?.is_dir() {
+            for child in &children {
+                self.delete_scanned(child)?;
+            }
+            fs::remove_dir(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+        self.tree.remove(path);
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+// Colors the text output: sizes by their magnitude, and paths like ls does,
+// following LS_COLORS
+
+use std::env;
+use std::io::{self, IsTerminal};
+
+use crossterm::style::{ContentStyle, Stylize};
+use lrg::LrgEntry;
+use lscolors::LsColors;
+
+// When to color the output
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn parse(name: &str) -> Option<ColorChoice> {
+        match name {
+            "auto" => Some(ColorChoice::Auto),
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            _ => None,
+        }
+    }
+
+    // Whether to color the output. Automatically, only terminals are colored,
+    // unless NO_COLOR is set to anything
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+            }
+        }
+    }
+}
+
+// Paints sizes and paths, or leaves them be if colors are off
+pub struct Painter {
+    ls_colors: Option<LsColors>,
+}
+
+impl Painter {
+    pub fn new(choice: ColorChoice) -> Painter {
+        let ls_colors = if choice.enabled() {
+            // Otherwise crossterm leaves out colors if NO_COLOR is set, even always
+            crossterm::style::force_color_output(true);
+            // Without LS_COLORS, use the same defaults as ls
+            Some(LsColors::from_env().unwrap_or_default())
+        } else {
+            None
+        };
+        Painter { ls_colors }
+    }
+
+    // Paints a size by its magnitude: bytes are dim, then kilobytes green,
+    // megabytes yellow and gigabytes or more red
+    pub fn size(&self, bytes: u64, text: &str) -> String {
+        if self.ls_colors.is_none() {
+            return text.to_string();
+        }
+        let styled = match bytes {
+            0..=0x3FF => text.dark_grey(),
+            0x400..=0xF_FFFF => text.green(),
+            0x10_0000..=0x3FFF_FFFF => text.yellow(),
+            _ => text.red().bold(),
+        };
+        styled.to_string()
+    }
+
+    // Paints the displayed path of an entry like ls would, by its type and name
+    pub fn path(&self, entry: &LrgEntry, text: &str) -> String {
+        let ls_colors = match &self.ls_colors {
+            Some(ls_colors) => ls_colors,
+            None => return text.to_string(),
+        };
+        let style = ls_colors
+            .style_for_path_with_metadata(entry.path(), entry.metadata())
+            .map(|style| style.to_crossterm_style())
+            .unwrap_or_default();
+        if style == ContentStyle::default() {
+            text.to_string()
+        } else {
+            style.apply(text).to_string()
+        }
+    }
+}
@@ -0,0 +1,126 @@
+// An ncdu-style browser of the tree of entries found, for drilling into
+// directories and deleting what takes up the space
+
+use std::io::{self, Write};
+
+use crossterm::event::{self, Event, KeyEventKind};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, queue};
+use humansize::file_size_opts;
+use lrg::TreeNode;
+
+use crate::browser::Browser;
+use crate::human;
+
+// The width of the bars showing how much of a directory each child takes up
+const BAR_WIDTH: usize = 10;
+
+const HELP: &str = "up/down: move  enter: open  left: back  s/n/m: sort by size/name/mtime  \
+                    .: hidden  space: mark  d: delete  q: quit";
+
+// Restores the terminal when dropped, even if browsing panics
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        terminal::enable_raw_mode()?;
+        queue!(io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        io::stdout().flush()?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = queue!(io::stdout(), cursor::Show, LeaveAlternateScreen);
+        let _ = io::stdout().flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// Draws the directory being listed, with a header and a status line
+fn draw(browser: &mut Browser, out: &mut impl Write, hs_options: &file_size_opts::FileSizeOpts) -> io::Result<()> {
+    let (width, height) = terminal::size()?;
+    let (width, rows) = (width as usize, (height as usize).saturating_sub(2));
+
+    // Keep the selection on the screen
+    if browser.selected < browser.scroll {
+        browser.scroll = browser.selected;
+    } else if rows > 0 && browser.selected >= browser.scroll + rows {
+        browser.scroll = browser.selected + 1 - rows;
+    }
+
+    let size = |bytes: u64| human(bytes, hs_options);
+    let current = browser.current();
+    let header = format!(
+        " {} ({}, sorted by {}{})",
+        current.path().display(),
+        size(current.size()),
+        browser.sort.name(),
+        if browser.show_hidden { "" } else { ", hiding hidden files" }
+    );
+    queue!(out, Clear(ClearType::All), cursor::MoveTo(0, 0), SetAttribute(Attribute::Reverse))?;
+    queue!(out, Print(fit(&header, width)), SetAttribute(Attribute::Reset))?;
+
+    let total = current.size();
+    let children = browser.children();
+    for (row, (i, child)) in children.iter().enumerate().skip(browser.scroll).take(rows).enumerate() {
+        let filled = if total == 0 { 0 } else { (child.size() as f64 / total as f64 * BAR_WIDTH as f64).round() as usize };
+        let line = format!(
+            "{} {:>10} [{:<bar_width$}] {}{}",
+            if browser.marked.contains(child.path()) { '*' } else { ' ' },
+            size(child.size()),
+            "#".repeat(filled.min(BAR_WIDTH)),
+            child.name().to_string_lossy(),
+            if child.is_dir() { "/" } else { "" },
+            bar_width = BAR_WIDTH
+        );
+        queue!(out, cursor::MoveTo(0, row as u16 + 1))?;
+        if i == browser.selected {
+            queue!(out, SetAttribute(Attribute::Reverse), Print(fit(&line, width)), SetAttribute(Attribute::Reset))?;
+        } else {
+            queue!(out, Print(fit(&line, width)))?;
+        }
+    }
+    if children.is_empty() {
+        queue!(out, cursor::MoveTo(0, 1), Print(fit("  (empty)", width)))?;
+    }
+
+    let status = if browser.pending.is_empty() {
+        browser.message.clone().unwrap_or_else(|| String::from(HELP))
+    } else {
+        format!(
+            "delete {} entries ({}), keeping what wasn't scanned? [y/N]",
+            browser.pending.len(),
+            size(browser.pending_size())
+        )
+    };
+    queue!(out, cursor::MoveTo(0, height.saturating_sub(1)), Print(fit(&status, width)))?;
+    out.flush()
+}
+
+// Cuts a line to the width of the screen, padding it so highlights fill the row
+fn fit(line: &str, width: usize) -> String {
+    let mut line: String = line.chars().take(width).collect();
+    let len = line.chars().count();
+    line.extend(std::iter::repeat_n(' ', width - len));
+    line
+}
+
+// Browses the tree until quit, deleting the entries confirmed for deletion
+pub fn browse(tree: TreeNode, hs_options: &file_size_opts::FileSizeOpts) -> io::Result<()> {
+    let mut browser = Browser::new(tree);
+
+    let _screen = Screen::enter()?;
+    let mut out = io::stdout();
+    loop {
+        draw(&mut browser, &mut out, hs_options)?;
+        if let Event::Key(key) = event::read()? {
+            let page = terminal::size().map_or(10, |(_, height)| height as isize - 2);
+            if key.kind == KeyEventKind::Press && browser.handle(key.code, page) {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -1,20 +1,121 @@
 extern crate lrg;
 extern crate clap;
+extern crate crossterm;
+extern crate csv;
 extern crate humansize;
 extern crate pathdiff;
+extern crate serde;
+extern crate serde_json;
 
+use std::collections::HashMap;
 use std::env;
-use std::path::{PathBuf};
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::io::{self, IsTerminal};
 use std::process;
+use std::time::SystemTime;
 
-use lrg::{get_walkdir_error_str, Lrg, LrgOptions, SortBy};
+use lrg::{absolute_path, ChangeKind, Direction, EntryGroup, EntryType, Ignored, Lrg, LrgEntry, LrgError, LrgOptions, LrgRecord, LrgStats, OwnerNames, SizeMetric, Snapshot, SortBy, SortKey, TreeNode, parse_duration, parse_size};
 
-use clap::{App, Arg};
+use clap::{App, Arg, SubCommand};
 use humansize::{file_size_opts, FileSize};
 use pathdiff::diff_paths;
+use serde::Serialize;
 
-// TODO colored output
-// TODO handle errors function options for lib
+mod browser;
+mod color;
+mod interactive;
+
+use color::{ColorChoice, Painter};
+
+// The formats entries can be printed in
+#[derive(PartialEq)]
+enum Format {
+    Text,
+    Json,
+    Ndjson,
+    Csv,
+    Tsv,
+}
+
+// The keys entries can be grouped by
+#[derive(Clone, Copy)]
+enum GroupBy {
+    Extension,
+    Owner,
+    Group,
+}
+
+// The columns printed in the csv and tsv formats
+#[derive(Clone, Copy)]
+enum Column {
+    Size,
+    HumanSize,
+    Path,
+    Mtime,
+    Owner,
+    Type,
+    Links,
+}
+
+impl Column {
+    const ALL: [Column; 7] = [
+        Column::Size,
+        Column::HumanSize,
+        Column::Path,
+        Column::Mtime,
+        Column::Owner,
+        Column::Type,
+        Column::Links,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Column::Size => "size",
+            Column::HumanSize => "human_size",
+            Column::Path => "path",
+            Column::Mtime => "mtime",
+            Column::Owner => "owner",
+            Column::Type => "type",
+            Column::Links => "links",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Column> {
+        Column::ALL.iter().cloned().find(|column| column.name() == name)
+    }
+}
+
+// How the results are printed, whichever way they're printed
+struct OutputOptions {
+    // The number of entries, sets, groups or changes to print
+    num_entries: usize,
+    absolute: bool,
+    quiet: bool,
+    hs_options: file_size_opts::FileSizeOpts,
+    painter: Painter,
+}
+
+// An entry printed as JSON, with everything needed to use it without lrg
+#[derive(Serialize)]
+struct JsonEntry {
+    #[serde(flatten)]
+    record: LrgRecord,
+    human_size: Option<String>,
+    absolute_path: PathBuf,
+    relative_path: PathBuf,
+}
+
+impl JsonEntry {
+    fn new(entry: &LrgEntry, base: &Path, hs_options: &file_size_opts::FileSizeOpts) -> JsonEntry {
+        JsonEntry {
+            record: LrgRecord::from(entry),
+            human_size: entry.known_size().map(|size| human(size, hs_options)),
+            absolute_path: absolute_path(entry.path()),
+            relative_path: PathBuf::from(display_link(entry.path(), base, false)),
+        }
+    }
+}
 
 fn main() {
     // Init env_logger
@@ -29,7 +130,7 @@ fn main() {
             .short("n")
             .long("number")
             .value_name("NUM_ENTRIES")
-            .help("sets the number of files to list (default: 5)")
+            .help("sets the number of files to list, or 'all' to list every file (default: 5)")
             .takes_value(true))
         .arg(Arg::with_name("RECURSIVE")
             .short("r")
@@ -45,14 +146,32 @@ fn main() {
             .short("l")
             .long("follow-links")
             .help("will follow links of files (default: false)"))
+        .arg(Arg::with_name("ONE_FILE_SYSTEM")
+            .short("x")
+            .long("one-file-system")
+            .help("skips directories on other file systems than the path to search in, such as mount points (default: false)"))
         .arg(Arg::with_name("DIRECTORIES")
             .short("i")
             .long("directories")
-            .help("include directories in search (default: false)"))
+            .help("include directories in search, sized by their total contents (default: false)"))
+        .arg(Arg::with_name("DISK_USAGE")
+            .long("disk-usage")
+            .help("sizes files by the disk space allocated for them, like du, instead of their apparent size (default: false)"))
+        .arg(Arg::with_name("DEDUP_LINKS")
+            .long("dedup-links")
+            .help("lists files with several hard links once, along with their other links, counting them once in directory sizes (default: false)"))
         .arg(Arg::with_name("ASCENDING")
             .short("a")
             .long("ascending")
             .help("sort the results in ascending order (default: false)"))
+        .arg(Arg::with_name("SORT")
+            .short("s")
+            .long("sort")
+            .value_name("KEYS")
+            .help("sorts the results by comma separated keys out of size, mtime, atime, ctime, name, path, depth and ext, ascending or descending if prefixed with -, such as size,-mtime (default: -size)")
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .conflicts_with("ASCENDING"))
         .arg(Arg::with_name("ABSOLUTE")
             .short("b")
             .long("absolute")
@@ -63,13 +182,166 @@ fn main() {
             .value_name("UNITS")
             .help("sets the units to display: decimal for 1000KB, binary for 1024KiB, conventional for 1024KB (default: conventional)")
             .takes_value(true))
+        .arg(Arg::with_name("MIN_SIZE")
+            .long("min-size")
+            .value_name("SIZE")
+            .help("only lists entries of at least the size, such as 10M, 1.5GiB or 512k, using the units' meaning of KB (default: 0)")
+            .takes_value(true))
+        .arg(Arg::with_name("MAX_SIZE")
+            .long("max-size")
+            .value_name("SIZE")
+            .help("only lists entries of at most the size, parsed like --min-size (default: max possible)")
+            .takes_value(true))
+        .arg(Arg::with_name("OLDER_THAN")
+            .long("older-than")
+            .value_name("DURATION")
+            .help("only lists entries last modified longer ago than the duration, such as 90d, 12h, 30m or 2w")
+            .takes_value(true))
+        .arg(Arg::with_name("NEWER_THAN")
+            .long("newer-than")
+            .value_name("DURATION")
+            .help("only lists entries last modified within the duration, parsed like --older-than")
+            .takes_value(true))
+        .arg(Arg::with_name("NEWER")
+            .long("newer")
+            .value_name("FILE")
+            .help("only lists entries last modified after the file was")
+            .takes_value(true)
+            .conflicts_with("NEWER_THAN"))
+        .arg(Arg::with_name("ACCESSED_OLDER_THAN")
+            .long("accessed-older-than")
+            .value_name("DURATION")
+            .help("only lists entries last accessed longer ago than the duration, parsed like --older-than")
+            .takes_value(true))
+        .arg(Arg::with_name("ACCESSED_NEWER_THAN")
+            .long("accessed-newer-than")
+            .value_name("DURATION")
+            .help("only lists entries last accessed within the duration, parsed like --older-than")
+            .takes_value(true))
+        .arg(Arg::with_name("CHANGED_OLDER_THAN")
+            .long("changed-older-than")
+            .value_name("DURATION")
+            .help("only lists entries whose metadata (such as permissions or owner) last changed longer ago than the \
+                   duration, parsed like --older-than (only on unix)")
+            .takes_value(true))
+        .arg(Arg::with_name("CHANGED_NEWER_THAN")
+            .long("changed-newer-than")
+            .value_name("DURATION")
+            .help("only lists entries whose metadata last changed within the duration, parsed like --older-than (only on \
+                   unix)")
+            .takes_value(true))
+        .arg(Arg::with_name("FORMAT")
+            .short("f")
+            .long("format")
+            .value_name("FORMAT")
+            .help("sets the output format: text for size and path lines, json for an array of objects, ndjson for an object per line, csv or tsv for a table with a header row (default: text)")
+            .takes_value(true))
+        .arg(Arg::with_name("COLOR")
+            .long("color")
+            .value_name("WHEN")
+            .help("sets when to color the text format, by size and like ls (following LS_COLORS): auto for only terminals, \
+                   unless NO_COLOR is set, always or never (default: auto)")
+            .takes_value(true))
+        .arg(Arg::with_name("COLUMNS")
+            .long("columns")
+            .value_name("COLUMNS")
+            .help("sets the comma separated columns of the csv and tsv formats, out of size, human_size, path, mtime, owner, type and links (the number of hard links) (default: all of them)")
+            .takes_value(true))
+        .arg(Arg::with_name("THREADS")
+            .short("t")
+            .long("threads")
+            .value_name("THREADS")
+            .help("sets the number of threads to search with (default: 1)")
+            .takes_value(true))
+        .arg(Arg::with_name("INCLUDE")
+            .long("include")
+            .value_name("GLOB")
+            .help("only searches files matching the glob, matched against the file name, or the relative path if it has a '/' (can be repeated)")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1))
+        .arg(Arg::with_name("EXCLUDE")
+            .long("exclude")
+            .value_name("GLOB")
+            .help("skips files and directories matching the glob, matched like --include (can be repeated)")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1))
+        .arg(Arg::with_name("RESPECT_GITIGNORE")
+            .long("respect-gitignore")
+            .help("skips files and directories ignored by .gitignore and .ignore files (default: false)"))
+        .arg(Arg::with_name("ONLY_IGNORED")
+            .long("only-ignored")
+            .help("only searches files ignored by .gitignore and .ignore files (default: false)")
+            .conflicts_with("RESPECT_GITIGNORE"))
+        .arg(Arg::with_name("GROUP_BY")
+            .long("group-by")
+            .value_name("KEY")
+            .help("lists the total size, number of files and largest file of each group of files instead, largest total first, \
+                   grouped by ext for their extension (ignoring case), owner for the user owning them or group for their group")
+            .takes_value(true)
+            .conflicts_with("SUMMARY"))
+        .arg(Arg::with_name("HISTOGRAM")
+            .long("histogram")
+            .help("prints a chart of how many files of each size were found instead, in bins of empty files, then smaller \
+                   than 1KiB, 10KiB and so on up to 10GiB, with their total size (default: false)")
+            .conflicts_with_all(&["GROUP_BY", "SUMMARY"]))
+        .arg(Arg::with_name("INTERACTIVE")
+            .long("interactive")
+            .help("browses the directories found and their sizes in the terminal instead, where entries can be marked and \
+                   deleted, keeping anything beneath them that wasn't scanned (default: false)")
+            .conflicts_with_all(&["GROUP_BY", "HISTOGRAM", "SUMMARY"]))
+        .arg(Arg::with_name("TREE")
+            .long("tree")
+            .help("prints the entries listed as a tree of the directories they're in, largest first, with the total size of \
+                   each directory (default: false)")
+            .conflicts_with_all(&["GROUP_BY", "HISTOGRAM", "INTERACTIVE"]))
+        .arg(Arg::with_name("SUMMARY")
+            .long("summary")
+            .help("prints statistics of every file found after the list, such as their total size and percentiles, and \
+                   how much of the total the listed files are, to stderr unless the format is text (default: false)"))
+        .arg(Arg::with_name("QUIET")
+            .short("q")
+            .long("quiet")
+            .help("only prints a summary of the errors encountered, instead of each one (default: false)"))
         .arg(Arg::with_name("FILEPATH")
             .help("the path to search in")
             .index(1))
+        .arg(Arg::with_name("SAVE")
+            .long("save")
+            .value_name("FILE")
+            .help("saves every file and directory found to the file, so later scans can be compared with it by diff")
+            .takes_value(true)
+            .conflicts_with_all(&["GROUP_BY", "HISTOGRAM", "INTERACTIVE", "TREE"]))
+        .subcommand(SubCommand::with_name("diff")
+            .about("lists the files and directories added, removed, grown or shrunk since a scan saved by --save, most \
+                    changed first (options go before diff)")
+            .arg(Arg::with_name("OLD")
+                .help("the saved scan to compare with")
+                .required(true)
+                .index(1))
+            .arg(Arg::with_name("NEW")
+                .help("a newer saved scan, or a directory to scan (default: the directory of the old scan)")
+                .index(2)))
+        .subcommand(SubCommand::with_name("dupes")
+            .about("finds sets of files with the same contents, most wasted space first (options go before dupes)")
+            .arg(Arg::with_name("FILEPATH")
+                .help("the path to search in")
+                .index(1)))
         .get_matches();
 
+    // Whether to find duplicate files instead of the largest ones
+    let dupes = matches.subcommand_matches("dupes");
+
+    // Whether to compare with a saved scan instead of finding the largest files
+    let diff = matches.subcommand_matches("diff");
+
+    // The file to save the scan to
+    let save = matches.value_of("SAVE").map(PathBuf::from);
+
     // Get directory to search
-    let current_dir = match matches.value_of("FILEPATH") {
+    let filepath = dupes.unwrap_or(&matches).value_of("FILEPATH");
+    let current_dir = match filepath {
         Some(filepath) => PathBuf::from(filepath),
         None => match env::current_dir() {
             Ok(path) => path.as_path().to_owned(),
@@ -82,6 +354,7 @@ fn main() {
 
     // Get number of files to get
     let num_entries = match matches.value_of("NUMBER") {
+        Some("all") => usize::MAX,
         Some(number) => match number.parse::<usize>() {
             Ok(number) => number,
             Err(_err) => {
@@ -104,21 +377,117 @@ fn main() {
                     process::exit(1);
                 }
             },
-            None => ::std::usize::MAX,
+            None => usize::MAX,
         }
     };
 
+    // Get sizes of entries to list, where KB means 1000 bytes with decimal units
+    let decimal = matches.value_of("UNITS") == Some("decimal");
+    let min_size = match matches.value_of("MIN_SIZE") {
+        Some(size) => match parse_size(size, decimal) {
+            Some(size) => size,
+            None => {
+                println!("Error: couldn't parse min size");
+                process::exit(1);
+            }
+        },
+        None => 0,
+    };
+    let max_size = match matches.value_of("MAX_SIZE") {
+        Some(size) => match parse_size(size, decimal) {
+            Some(size) => size,
+            None => {
+                println!("Error: couldn't parse max size");
+                process::exit(1);
+            }
+        },
+        None => u64::MAX,
+    };
+
+    // Get times the entries to list were modified, accessed and changed, relative to now
+    let now = SystemTime::now();
+    let time_ago = |name: &str, message: &str| matches.value_of(name).map(|duration| {
+        match parse_duration(duration).and_then(|duration| now.checked_sub(duration)) {
+            Some(time) => time,
+            None => {
+                println!("Error: couldn't parse {}", message);
+                process::exit(1);
+            }
+        }
+    });
+    let modified_before = time_ago("OLDER_THAN", "older than duration");
+    let modified_after = match matches.value_of("NEWER") {
+        Some(file) => match fs::metadata(file).and_then(|metadata| metadata.modified()) {
+            Ok(time) => Some(time),
+            Err(_err) => {
+                println!("Error: couldn't get modification time of {}", file);
+                process::exit(1);
+            }
+        },
+        None => time_ago("NEWER_THAN", "newer than duration"),
+    };
+    let accessed_before = time_ago("ACCESSED_OLDER_THAN", "accessed older than duration");
+    let accessed_after = time_ago("ACCESSED_NEWER_THAN", "accessed newer than duration");
+    let changed_before = time_ago("CHANGED_OLDER_THAN", "changed older than duration");
+    let changed_after = time_ago("CHANGED_NEWER_THAN", "changed newer than duration");
+
+    // Get number of threads to search with
+    let threads = match matches.value_of("THREADS") {
+        Some(threads) => match threads.parse::<usize>() {
+            Ok(threads) if threads > 0 => threads,
+            _ => {
+                println!("Error: couldn't parse number of threads");
+                process::exit(1);
+            }
+        },
+        None => 1,
+    };
+
     // Whether to follow links or not
     let follow_links = matches.is_present("FOLLOW_LINKS");
 
+    // Whether to stay on the file system of the path to search in
+    let same_file_system = matches.is_present("ONE_FILE_SYSTEM");
+
     // Whether to include directories or not
     let include_dirs = matches.is_present("DIRECTORIES");
 
-    // Whether to sort by ascending or not
-    let sort_value = if matches.is_present("ASCENDING") {
-        SortBy::Ascending
+    // Whether to size files by their allocated blocks or apparent size
+    let size_metric = if matches.is_present("DISK_USAGE") {
+        SizeMetric::Allocated
     } else {
-        SortBy::Descending
+        SizeMetric::Apparent
+    };
+
+    // Globs of files to include and exclude
+    let include: Vec<String> = matches.values_of("INCLUDE")
+        .map_or_else(Vec::new, |globs| globs.map(String::from).collect());
+    let exclude: Vec<String> = matches.values_of("EXCLUDE")
+        .map_or_else(Vec::new, |globs| globs.map(String::from).collect());
+
+    // How to search files ignored by git
+    let ignored = if matches.is_present("RESPECT_GITIGNORE") {
+        Ignored::Skip
+    } else if matches.is_present("ONLY_IGNORED") {
+        Ignored::Only
+    } else {
+        Ignored::Include
+    };
+
+    // Whether to list hard links to the same file once
+    let dedup_links = matches.is_present("DEDUP_LINKS");
+
+    // What to sort by, or whether to sort by ascending size or not
+    let sort_value = match matches.value_of("SORT") {
+        Some(sort) => match parse_sort(sort) {
+            Some(sort) => sort,
+            None => {
+                println!("Error: couldn't parse sort");
+                process::exit(1);
+            }
+        },
+        None if matches.is_present("ASCENDING") => SortBy::Ascending,
+        None => SortBy::Descending,
     };
 
     // Whether to output absolute or relative values
@@ -140,64 +509,835 @@ fn main() {
         None => file_size_opts::CONVENTIONAL,
     };
 
+    // Parse format to print entries in
+    let format = match matches.value_of("FORMAT") {
+        Some("text") | None => Format::Text,
+        Some("json") => Format::Json,
+        Some("ndjson") => Format::Ndjson,
+        Some("csv") => Format::Csv,
+        Some("tsv") => Format::Tsv,
+        Some(_) => {
+            println!("Error: couldn't parse format");
+            process::exit(1);
+        }
+    };
+
+    // Parse key to group entries by
+    let group_by = match matches.value_of("GROUP_BY") {
+        Some("ext") => Some(GroupBy::Extension),
+        Some("owner") => Some(GroupBy::Owner),
+        Some("group") => Some(GroupBy::Group),
+        Some(_) => {
+            println!("Error: couldn't parse group by key");
+            process::exit(1);
+        }
+        None => None,
+    };
+
+    // Parse when to color the text format
+    let color = match matches.value_of("COLOR") {
+        Some(when) => match ColorChoice::parse(when) {
+            Some(color) => color,
+            None => {
+                println!("Error: couldn't parse color");
+                process::exit(1);
+            }
+        },
+        None => ColorChoice::Auto,
+    };
+
+    // Parse columns to print in the csv and tsv formats
+    let columns: Vec<Column> = match matches.value_of("COLUMNS") {
+        Some(columns) => match columns.split(',').map(|name| Column::parse(name.trim())).collect() {
+            Some(columns) => columns,
+            None => {
+                println!("Error: couldn't parse columns");
+                process::exit(1);
+            }
+        },
+        None => Column::ALL.to_vec(),
+    };
+
+    // Whether to print each error or just the summary
+    let quiet = matches.is_present("QUIET");
+
+    // Whether to print statistics of every file found
+    let summary = matches.is_present("SUMMARY");
+
+    // Whether to print a chart of the sizes of every file found
+    let histogram = matches.is_present("HISTOGRAM");
+
+    // Whether to browse the entries found in the terminal
+    let interactive = matches.is_present("INTERACTIVE");
+
+    // Whether to print the entries as a tree of directories
+    let tree = matches.is_present("TREE");
+
     // Set options for finding entries
     let options = LrgOptions {
         max_depth,
         follow_links,
         include_dirs,
+        threads,
+        include,
+        exclude,
+        ignored,
+        min_size,
+        max_size,
+        modified_before,
+        modified_after,
+        accessed_before,
+        accessed_after,
+        changed_before,
+        changed_after,
+        size_metric,
+        dedup_links,
+        same_file_system,
         ..LrgOptions::default()
     };
 
-    // Fetch entries
-    let entries = Lrg::new(&current_dir, &options)
-        .sort_by(&sort_value)
-        .get_entries();
+    let output = OutputOptions {
+        num_entries,
+        absolute: output_absolute,
+        quiet,
+        // Options for printing humansize'd numbers
+        hs_options: file_size_opts::FileSizeOpts {
+            allow_negative: true,
+            ..units
+        },
+        // Only the text format is colored
+        painter: Painter::new(if format == Format::Text { color } else { ColorChoice::Never }),
+    };
+
+    if let Some(diff) = diff {
+        if format != Format::Text {
+            println!("Error: diff only supports the text format");
+            process::exit(1);
+        }
+        // Unwrap since OLD is required
+        let old = Path::new(diff.value_of("OLD").unwrap());
+        let new = diff.value_of("NEW").map(Path::new);
+        print_diff(old, new, &options, save.as_deref(), &output);
+        return;
+    }
+
+    if dupes.is_some() {
+        if format != Format::Text {
+            println!("Error: dupes only supports the text format");
+            process::exit(1);
+        }
+        print_dupes(&current_dir, &options, &output);
+        return;
+    }
+
+    if interactive {
+        if format != Format::Text {
+            println!("Error: --interactive only supports the text format");
+            process::exit(1);
+        }
+        if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            println!("Error: --interactive needs a terminal");
+            process::exit(1);
+        }
+        browse(&current_dir, &options, &output);
+        return;
+    }
+
+    if histogram {
+        if format != Format::Text {
+            println!("Error: --histogram only supports the text format");
+            process::exit(1);
+        }
+        print_histogram(&current_dir, &options, &output);
+        return;
+    }
+
+    if let Some(group_by) = group_by {
+        if format != Format::Text {
+            println!("Error: --group-by only supports the text format");
+            process::exit(1);
+        }
+        print_groups(&current_dir, &options, group_by, &output);
+        return;
+    }
+
+    if tree {
+        if format != Format::Text {
+            println!("Error: --tree only supports the text format");
+            process::exit(1);
+        }
+        print_tree(&current_dir, &options, &sort_value, summary, &output);
+        return;
+    }
+
+    // Fetch entries, keeping every entry found if they're saved
+    let (lrg, entries) = match &save {
+        Some(file) => {
+            let all_options = LrgOptions {
+                include_dirs: true,
+                ..options.clone()
+            };
+            let mut lrg = Lrg::new(&current_dir, &all_options);
+            save_snapshot(&lrg, &current_dir, file);
+            let entries: Vec<LrgEntry> = lrg
+                .sort_by(&sort_value)
+                .get_entries()
+                .into_iter()
+                .filter(|entry| include_dirs || !entry.file_type().is_dir())
+                .take(num_entries)
+                .collect();
+            (lrg, entries)
+        }
+        // Only keep the size of every file if they're summarized
+        None if summary => {
+            let lrg = Lrg::new_top_n_with_stats(&current_dir, &options, &sort_value, num_entries);
+            let entries = lrg.get_entries();
+            (lrg, entries)
+        }
+        None => {
+            let lrg = Lrg::new_top_n(&current_dir, &options, &sort_value, num_entries);
+            let entries = lrg.get_entries();
+            (lrg, entries)
+        }
+    };
+
+    // Report errors encountered while searching, and mount points skipped
+    let errors = lrg.get_errors();
+    report_errors(&errors, &lrg.get_skipped_mounts(), quiet);
 
     // Check for no entries found
     if entries.is_empty() {
-        println!("lrg: no files found");
+        print_error_summary(&errors);
+        // Keep the output parseable for the other formats
+        match format {
+            Format::Text => println!("lrg: no files found"),
+            Format::Json => {
+                println!("[]");
+                eprintln!("lrg: no files found");
+            }
+            Format::Ndjson => eprintln!("lrg: no files found"),
+            Format::Csv | Format::Tsv => {
+                print_delimited(&entries, &columns, &format, &current_dir, &output);
+                eprintln!("lrg: no files found");
+            }
+        }
         process::exit(1);
     }
 
-    // Options for printing humansize'd numbers
-    let hs_options = file_size_opts::FileSizeOpts {
-        allow_negative: true,
-        ..units
+    match format {
+        Format::Text => print_text(&entries, &current_dir, &output),
+        Format::Json => {
+            let json: Vec<JsonEntry> = entries
+                .iter()
+                .map(|entry| JsonEntry::new(entry, &current_dir, &output.hs_options))
+                .collect();
+            // Unwrap since entries always serialize
+            println!("{}", serde_json::to_string_pretty(&json).unwrap());
+        }
+        Format::Ndjson => {
+            for entry in &entries {
+                let json = JsonEntry::new(entry, &current_dir, &output.hs_options);
+                println!("{}", serde_json::to_string(&json).unwrap());
+            }
+        }
+        Format::Csv | Format::Tsv => {
+            print_delimited(&entries, &columns, &format, &current_dir, &output);
+        }
+    }
+
+    if summary {
+        print_summary(&lrg.get_stats(), &entries, &format, &output.hs_options);
+    }
+
+    print_error_summary(&errors);
+}
+
+// Finds and prints the sets of duplicate files, with the most wasted space first
+fn print_dupes(path: &Path, options: &LrgOptions, output: &OutputOptions) {
+    let lrg = Lrg::new(path, options);
+    let duplicates = lrg.find_duplicates();
+
+    let mut errors = lrg.get_errors();
+    errors.extend(duplicates.get_errors());
+    report_errors(&errors, &lrg.get_skipped_mounts(), output.quiet);
+
+    let groups = duplicates.get_groups();
+    if groups.is_empty() {
+        print_error_summary(&errors);
+        println!("lrg: no duplicate files found");
+        process::exit(1);
+    }
+
+    for group in groups.iter().take(output.num_entries) {
+        println!(
+            "{} x {} ({} wasted):",
+            group.entries().len(),
+            human(group.size(), &output.hs_options),
+            human(group.wasted(), &output.hs_options)
+        );
+        for entry in group.entries() {
+            println!("    {}", display_path(entry, path, output.absolute));
+        }
+    }
+
+    print_error_summary(&errors);
+}
+
+// Groups the files found and prints the totals of each group, largest first
+fn print_groups(path: &Path, options: &LrgOptions, group_by: GroupBy, output: &OutputOptions) {
+    let lrg = Lrg::new(path, options);
+
+    let errors = lrg.get_errors();
+    report_errors(&errors, &lrg.get_skipped_mounts(), output.quiet);
+
+    let listed = match group_by {
+        GroupBy::Extension => print_group_list(
+            &lrg.group_by_extension(),
+            |extension| extension.clone().unwrap_or_else(|| String::from("(no extension)")),
+            path,
+            output,
+        ),
+        GroupBy::Owner => {
+            let names = OwnerNames::load();
+            print_group_list(
+                &lrg.group_by(LrgEntry::uid),
+                |uid| owner_name(*uid, |uid| names.user(uid)),
+                path,
+                output,
+            )
+        }
+        GroupBy::Group => {
+            let names = OwnerNames::load();
+            print_group_list(
+                &lrg.group_by(LrgEntry::gid),
+                |gid| owner_name(*gid, |gid| names.group(gid)),
+                path,
+                output,
+            )
+        }
     };
+    print_error_summary(&errors);
+    if !listed {
+        println!("lrg: no files found");
+        process::exit(1);
+    }
+}
 
-    // Iterate through entries
-    for (i, entry) in entries.iter().enumerate() {
-        // Break at number of requested entries
-        if i == num_entries {
-            break;
+// Prints a bar chart of the number of files found of each size, with the
+// total size of the files in each bin
+fn print_histogram(path: &Path, options: &LrgOptions, output: &OutputOptions) {
+    // Only the sizes of the files are needed, so no entries are kept
+    let lrg = Lrg::new_top_n_with_stats(path, options, &SortBy::Descending, 0);
+
+    let errors = lrg.get_errors();
+    report_errors(&errors, &lrg.get_skipped_mounts(), output.quiet);
+
+    let bins = lrg.histogram();
+    if bins.iter().all(|bin| bin.count() == 0) {
+        print_error_summary(&errors);
+        println!("lrg: no files found");
+        process::exit(1);
+    }
+
+    const BAR_WIDTH: u64 = 40;
+    // The bins are powers of 1024, so they're labelled in binary units whatever
+    // the units used for the totals
+    let bound = |bytes: u64| human(bytes, &file_size_opts::BINARY);
+    let labels: Vec<String> = bins
+        .iter()
+        .map(|bin| match bin.max() {
+            Some(1) => bound(0),
+            Some(max) => format!("< {}", bound(max)),
+            None => format!(">= {}", bound(bin.min())),
+        })
+        .collect();
+    let label_width = labels.iter().map(|label| label.len()).max().unwrap_or(0);
+    let max_count = bins.iter().map(|bin| bin.count()).max().unwrap_or(0);
+    let count_width = max_count.to_string().len();
+
+    for (bin, label) in bins.iter().zip(&labels) {
+        // Bins with any files always get a bar, however short
+        let width = (bin.count() * BAR_WIDTH).div_ceil(max_count);
+        println!(
+            "{:>label_width$} |{:<bar_width$}| {:>count_width$} file(s), {}",
+            label,
+            "#".repeat(width as usize),
+            bin.count(),
+            human(bin.total(), &output.hs_options),
+            label_width = label_width,
+            bar_width = BAR_WIDTH as usize,
+            count_width = count_width
+        );
+    }
+
+    print_error_summary(&errors);
+}
+
+// Scans every file and directory, then browses them in the terminal
+fn browse(path: &Path, options: &LrgOptions, output: &OutputOptions) {
+    // Every entry is browsed, so directories are shown with all their files
+    let lrg = Lrg::new(path, &unfiltered(options));
+
+    // Errors would be hidden by the browser, so only the summary is printed after
+    let errors = lrg.get_errors();
+    if let Err(err) = interactive::browse(lrg.tree(path), &output.hs_options) {
+        eprintln!("lrg: couldn't browse entries: {}", err);
+        process::exit(1);
+    }
+    print_error_summary(&errors);
+}
+
+// Gets options finding every directory and every file beneath them, without
+// the sizes and times to list
+fn unfiltered(options: &LrgOptions) -> LrgOptions {
+    LrgOptions {
+        include_dirs: true,
+        min_size: 0,
+        max_size: u64::MAX,
+        modified_before: None,
+        modified_after: None,
+        accessed_before: None,
+        accessed_after: None,
+        changed_before: None,
+        changed_after: None,
+        ..options.clone()
+    }
+}
+
+// Prints the entries listed as a tree of the directories they're in, with the
+// total size of each directory
+fn print_tree(path: &Path, options: &LrgOptions, sort: &SortBy, summary: bool, output: &OutputOptions) {
+    // Directories are needed for their total sizes, even if they aren't listed
+    // or aren't within the sizes and times to list, so only the entries listed
+    // are filtered by them
+    let mut lrg = Lrg::new(path, &unfiltered(options));
+
+    let errors = lrg.get_errors();
+    report_errors(&errors, &lrg.get_skipped_mounts(), output.quiet);
+
+    let entries = lrg.sort_by(sort).get_entries();
+    let found: Vec<&LrgEntry> = entries.iter().filter(|entry| options.matches_filters(entry)).collect();
+    let listed: Vec<LrgEntry> = found
+        .iter()
+        .filter(|entry| options.include_dirs || !entry.file_type().is_dir())
+        .take(output.num_entries)
+        .map(|entry| (*entry).clone())
+        .collect();
+    if listed.is_empty() {
+        print_error_summary(&errors);
+        println!("lrg: no files found");
+        process::exit(1);
+    }
+
+    // The directories the listed entries are in, up to the path searched in
+    let dirs: HashMap<&Path, &LrgEntry> = entries
+        .iter()
+        .filter(|entry| entry.file_type().is_dir())
+        .map(|entry| (entry.path(), entry))
+        .collect();
+    let mut nodes: HashMap<&Path, &LrgEntry> = HashMap::new();
+    for entry in &listed {
+        for dir in entry.path().ancestors().take_while(|dir| dir.starts_with(path)) {
+            if let Some(dir) = dirs.get(dir) {
+                nodes.insert(dir.path(), dir);
+            }
         }
+    }
+    let nodes: Vec<LrgEntry> = nodes.into_values().cloned().chain(listed.iter().cloned()).collect();
+    let tree = TreeNode::new(path, &nodes);
 
-        // Get the path to display depending on flags
-        let display_path = if output_absolute {
-            format!("{}", entry.path().display())
-        } else {
-            format!("{}", diff_paths(entry.path(), &current_dir).unwrap_or_else(|| PathBuf::new()).display())
+    let root = if output.absolute { absolute_path(path) } else { path.to_path_buf() };
+    println!("{} ({})", root.display(), human(tree.size(), &output.hs_options));
+    print_tree_children(&tree, "", output);
 
+    // The stats only count the files within the sizes and times to list, as
+    // they would have been found
+    if summary {
+        let mut sizes: Vec<u64> = found
+            .iter()
+            .filter(|entry| !entry.file_type().is_dir())
+            .map(|entry| entry.size())
+            .collect();
+        sizes.sort_unstable();
+        print_summary(&LrgStats::new(&sizes, errors.len()), &listed, &Format::Text, &output.hs_options);
+    }
+
+    print_error_summary(&errors);
+}
+
+// Prints the children of a node of a tree below it, with lines joining them
+fn print_tree_children(node: &TreeNode, prefix: &str, output: &OutputOptions) {
+    let children = node.children();
+    for (i, child) in children.iter().enumerate() {
+        let last = i + 1 == children.len();
+        let mut name = child.name().to_string_lossy().into_owned();
+        if child.is_dir() {
+            name.push('/');
+        }
+        let name = match child.entry() {
+            Some(entry) => output.painter.path(entry, &name),
+            None => name,
+        };
+        let size = match child.entry() {
+            Some(entry) => display_size(entry, &output.hs_options),
+            None => human(child.size(), &output.hs_options),
         };
+        println!(
+            "{}{}{} ({})",
+            prefix,
+            if last { "└── " } else { "├── " },
+            name,
+            output.painter.size(child.size(), &size)
+        );
+        let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+        print_tree_children(child, &prefix, output);
+    }
+}
 
-        // Handle error getting metadata
-        match entry.metadata() {
-            Ok(meta) => {
-                // Unwrap since guranteed to not panic due to options
-                println!(
-                    "{}: {}",
-                    meta.len().file_size(&hs_options).unwrap(),
-                    display_path
-                );
-            }
-            Err(err) => {
-                let error_message = get_walkdir_error_str(&err);
-                println!(
-                    "lrg: cannot get metadata of '{}': {}",
-                    display_path,
-                    error_message
-                );
+// Saves every entry found to a file, exiting if it couldn't be
+fn save_snapshot(lrg: &Lrg, path: &Path, file: &Path) {
+    if let Err(err) = lrg.snapshot(path).save(file) {
+        eprintln!("lrg: couldn't save scan to '{}': {}", file.display(), err);
+        process::exit(1);
+    }
+}
+
+// Compares a saved scan with a newer one, or a new scan, and prints the entries
+// that changed, most changed first
+fn print_diff(old: &Path, new: Option<&Path>, options: &LrgOptions, save: Option<&Path>, output: &OutputOptions) {
+    let load = |file: &Path| match Snapshot::load(file) {
+        Ok(snapshot) => snapshot,
+        Err(err) => {
+            eprintln!("lrg: couldn't read scan '{}': {}", file.display(), err);
+            process::exit(1);
+        }
+    };
+    let old = load(old);
+
+    // Scan a directory, or the one the old scan was of, unless given a scan
+    let mut errors = Vec::new();
+    let new = match new {
+        Some(new) if !new.is_dir() => load(new),
+        new => {
+            let path = new.unwrap_or_else(|| old.root());
+            let all_options = LrgOptions {
+                include_dirs: true,
+                ..options.clone()
+            };
+            let lrg = Lrg::new(path, &all_options);
+            errors = lrg.get_errors();
+            report_errors(&errors, &lrg.get_skipped_mounts(), output.quiet);
+            if let Some(file) = save {
+                save_snapshot(&lrg, path, file);
             }
+            lrg.snapshot(path)
+        }
+    };
+
+    let changes = old.diff(&new);
+    if changes.is_empty() {
+        print_error_summary(&errors);
+        println!("lrg: no changes found");
+        process::exit(1);
+    }
+
+    let changes = &changes[..output.num_entries.min(changes.len())];
+    let deltas: Vec<String> = changes
+        .iter()
+        .map(|change| {
+            let delta = human(change.delta(), &output.hs_options);
+            if change.delta() > 0 { format!("+{}", delta) } else { delta }
+        })
+        .collect();
+    let width = deltas.iter().map(|delta| delta.chars().count()).max().unwrap_or(0);
+
+    for (change, delta) in changes.iter().zip(&deltas) {
+        // The root itself has an empty path
+        let path = match (change.path().as_os_str().is_empty(), output.absolute) {
+            (true, true) => new.root().display().to_string(),
+            (true, false) => String::from("."),
+            (false, true) => new.root().join(change.path()).display().to_string(),
+            (false, false) => change.path().display().to_string(),
+        };
+        let slash = change.file_type() == EntryType::Dir && !change.path().as_os_str().is_empty();
+        let size = |bytes: Option<u64>| human(bytes.unwrap_or(0), &output.hs_options);
+        let kind = match change.kind() {
+            ChangeKind::Added => String::from("added"),
+            ChangeKind::Removed => String::from("removed"),
+            ChangeKind::Grown => format!("grown from {} to {}", size(change.old_size()), size(change.new_size())),
+            ChangeKind::Shrunk => format!("shrunk from {} to {}", size(change.old_size()), size(change.new_size())),
+        };
+        println!(
+            "{}{}  {}{} ({})",
+            " ".repeat(width - delta.chars().count()),
+            output.painter.size(change.delta().unsigned_abs(), delta),
+            path,
+            if slash { "/" } else { "" },
+            kind
+        );
+    }
+
+    print_error_summary(&errors);
+}
+
+// Gets the name of a user or group, or its id if it has no name
+fn owner_name<'a, F>(id: Option<u32>, name: F) -> String
+where
+    F: Fn(u32) -> Option<&'a str>,
+{
+    match id {
+        Some(id) => name(id).map_or_else(|| id.to_string(), String::from),
+        None => String::from("(unknown)"),
+    }
+}
+
+// Prints each group as its total size, name, number of files and largest file,
+// returning whether there were any groups to print
+fn print_group_list<K, F>(groups: &[EntryGroup<K>], name: F, base: &Path, output: &OutputOptions) -> bool
+where
+    F: Fn(&K) -> String,
+{
+    for group in groups.iter().take(output.num_entries) {
+        println!(
+            "{}: {} ({} file(s), largest: {})",
+            human(group.total(), &output.hs_options),
+            name(group.key()),
+            group.count(),
+            display_path(group.largest(), base, output.absolute)
+        );
+    }
+    !groups.is_empty()
+}
+
+// Prints the errors encountered while searching and the mount points skipped,
+// or only the number of mount points skipped if quiet
+fn report_errors(errors: &[LrgError], skipped_mounts: &[PathBuf], quiet: bool) {
+    if quiet {
+        if !skipped_mounts.is_empty() {
+            eprintln!("lrg: skipped {} mount point(s)", skipped_mounts.len());
+        }
+    } else {
+        for error in errors {
+            eprintln!("lrg: {}", error);
+        }
+        for mount_point in skipped_mounts {
+            eprintln!("lrg: skipped mount point '{}'", mount_point.display());
+        }
+    }
+}
+
+// Prints each entry as its size and path, in a table with the sizes aligned.
+// If any file has several hard links, how many each file has is shown too
+fn print_text(entries: &[LrgEntry], base: &Path, output: &OutputOptions) {
+    let sizes: Vec<String> = entries.iter().map(|entry| display_size(entry, &output.hs_options)).collect();
+    let width = sizes.iter().map(|size| size.chars().count()).max().unwrap_or(0);
+
+    // Directories and files whose metadata couldn't be read have no count
+    let link_counts: Vec<Option<u64>> = entries
+        .iter()
+        .map(|entry| entry.link_count().filter(|_| !entry.file_type().is_dir()))
+        .collect();
+    let links_width = if link_counts.iter().any(|count| count.is_some_and(|count| count > 1)) {
+        link_counts.iter().flatten().map(|count| count.to_string().len()).max().unwrap_or(0)
+    } else {
+        0
+    };
+
+    for ((entry, size), count) in entries.iter().zip(&sizes).zip(&link_counts) {
+        let count = match count {
+            Some(count) if links_width > 0 => format!("{:>width$}  ", count, width = links_width),
+            _ if links_width > 0 => " ".repeat(links_width + 2),
+            _ => String::new(),
+        };
+        // The other links found, if links were deduplicated
+        let links = if entry.links().is_empty() {
+            String::new()
+        } else {
+            let links: Vec<String> = entry
+                .links()
+                .iter()
+                .map(|link| display_link(link, base, output.absolute))
+                .collect();
+            format!(" (also at: {})", links.join(", "))
+        };
+
+        // Pad before painting, since colors take up no space
+        println!(
+            "{}{}  {}{}{}",
+            " ".repeat(width - size.chars().count()),
+            output.painter.size(entry.size(), size),
+            count,
+            output.painter.path(entry, &display_path(entry, base, output.absolute)),
+            links
+        );
+    }
+}
+
+// Formats a number of bytes in the units of the options
+fn human<T: FileSize>(bytes: T, hs_options: &file_size_opts::FileSizeOpts) -> String {
+    // Unwrap since guranteed to not panic due to options
+    bytes.file_size(hs_options).unwrap()
+}
+
+// Formats the entry's size, or ? if it couldn't be read
+fn display_size(entry: &LrgEntry, hs_options: &file_size_opts::FileSizeOpts) -> String {
+    match entry.known_size() {
+        Some(size) => human(size, hs_options),
+        None => String::from("?"),
+    }
+}
+
+// Prints the statistics of every file found, and how much of the total size
+// the listed files are. Only printed to stdout for text, to keep the others parseable
+fn print_summary(stats: &LrgStats, entries: &[LrgEntry], format: &Format, hs_options: &file_size_opts::FileSizeOpts) {
+    let listed: Vec<u64> = entries
+        .iter()
+        .filter(|entry| !entry.file_type().is_dir())
+        .map(|entry| entry.size())
+        .collect();
+    let listed_bytes: u64 = listed.iter().sum();
+    let percent = if stats.total_bytes == 0 {
+        0.0
+    } else {
+        listed_bytes as f64 / stats.total_bytes as f64 * 100.0
+    };
+
+    let size = |bytes: u64| human(bytes, hs_options);
+    let lines = [
+        String::from("summary:"),
+        format!("    files:    {} ({} listed)", stats.files, listed.len()),
+        format!("    total:    {} ({} listed, {:.2}%)", size(stats.total_bytes), size(listed_bytes), percent),
+        format!("    mean:     {}", size(stats.mean.round() as u64)),
+        format!("    median:   {}", size(stats.median)),
+        format!("    p90:      {}", size(stats.p90)),
+        format!("    p99:      {}", size(stats.p99)),
+        format!("    largest:  {}", size(stats.largest)),
+        format!("    smallest: {}", size(stats.smallest)),
+        format!("    errors:   {}", stats.errors),
+    ];
+    for line in &lines {
+        if *format == Format::Text {
+            println!("{}", line);
+        } else {
+            eprintln!("{}", line);
+        }
+    }
+}
+
+// Prints the entries as a table with a header row, separated by commas or tabs
+fn print_delimited(entries: &[LrgEntry], columns: &[Column], format: &Format, base: &Path, output: &OutputOptions) {
+    let delimiter = if *format == Format::Tsv { b'\t' } else { b',' };
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(io::stdout());
+
+    let names = OwnerNames::load();
+    let header: Vec<&str> = columns.iter().map(|column| column.name()).collect();
+    let mut result = writer.write_record(&header);
+    for entry in entries {
+        if result.is_err() {
+            break;
+        }
+        let record = LrgRecord::from(entry);
+        let row: Vec<String> = columns
+            .iter()
+            .map(|column| match column {
+                Column::Size => entry.known_size().map_or_else(String::new, |size| size.to_string()),
+                Column::HumanSize => entry.known_size().map_or_else(String::new, |size| human(size, &output.hs_options)),
+                Column::Path => display_path(entry, base, output.absolute),
+                Column::Mtime => record.modified.map_or_else(String::new, |time| time.to_string()),
+                Column::Owner => owner(entry, &names),
+                Column::Type => record.file_type.to_string(),
+                Column::Links => record.link_count.map_or_else(String::new, |count| count.to_string()),
+            })
+            .collect();
+        result = writer.write_record(&row);
+    }
+    if let Err(err) = result.and_then(|_| writer.flush().map_err(csv::Error::from)) {
+        eprintln!("lrg: couldn't write output: {}", err);
+        process::exit(1);
+    }
+}
+
+// Gets the path to display depending on flags
+fn display_path(entry: &LrgEntry, base: &Path, output_absolute: bool) -> String {
+    display_link(entry.path(), base, output_absolute)
+}
+
+// Gets a path found while searching to display depending on flags
+fn display_link(path: &Path, base: &Path, output_absolute: bool) -> String {
+    if output_absolute {
+        format!("{}", path.display())
+    } else {
+        match diff_paths(path, base) {
+            // The path searched in, which is listed with directories
+            Some(path) if path.as_os_str().is_empty() => String::from("."),
+            path => format!("{}", path.unwrap_or_default().display()),
         }
     }
 }
+
+// Gets the name of the entry's owner, or their user id if they have no name,
+// or nothing if it couldn't be read
+fn owner(entry: &LrgEntry, names: &OwnerNames) -> String {
+    entry.uid().map_or_else(String::new, |uid| owner_name(Some(uid), |uid| names.user(uid)))
+}
+
+// Prints the number of errors encountered, by kind
+fn print_error_summary(errors: &[LrgError]) {
+    if errors.is_empty() {
+        return;
+    }
+
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for error in errors {
+        let kind = match error {
+            LrgError::PermissionDenied(_) => "permission denied",
+            LrgError::NotFound(_) => "not found",
+            LrgError::LoopDetected { .. } => "link loop",
+            LrgError::Metadata(_, _) => "metadata",
+            LrgError::Io(_, _) => "other",
+            LrgError::InvalidPattern(_, _) => "invalid pattern",
+        };
+        match counts.iter_mut().find(|(name, _)| *name == kind) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((kind, 1)),
+        }
+    }
+
+    let counts: Vec<String> = counts
+        .iter()
+        .map(|(kind, count)| format!("{} {}", count, kind))
+        .collect();
+    eprintln!(
+        "lrg: encountered {} error(s): {}",
+        errors.len(),
+        counts.join(", ")
+    );
+}
+
+// Parses comma separated sort keys, such as mtime for oldest first or -mtime for
+// newest first
+fn parse_sort(sort: &str) -> Option<SortBy> {
+    let keys: Option<Vec<(SortKey, Direction)>> = sort.split(',').map(parse_sort_key).collect();
+    Some(SortBy::Keys(keys?))
+}
+
+fn parse_sort_key(key: &str) -> Option<(SortKey, Direction)> {
+    let (key, direction) = match key.trim().strip_prefix('-') {
+        Some(key) => (key, Direction::Descending),
+        None => (key.trim(), Direction::Ascending),
+    };
+    let key = match key {
+        "size" => SortKey::Size,
+        "mtime" => SortKey::Modified,
+        "atime" => SortKey::Accessed,
+        "ctime" => SortKey::Changed,
+        "name" => SortKey::Name,
+        "path" => SortKey::Path,
+        "depth" => SortKey::Depth,
+        "ext" | "extension" => SortKey::Extension,
+        _ => return None,
+    };
+    Some((key, direction))
+}
@@ -0,0 +1,175 @@
+//! Finds files with the same contents among the entries of an [`Lrg`].
+//!
+//! Files are only compared if they have the same size, then only read in full
+//! if the start of them hashes the same, so most files are never read.
+//!
+//! [`Lrg`]: ../struct.Lrg.html
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, Read};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+
+use crate::{inode, LrgEntry, LrgError};
+
+// How much of each file is hashed before hashing the whole file
+const PARTIAL_SIZE: u64 = 4096;
+
+/// A set of files with the same contents.
+#[derive(Clone, Debug)]
+pub struct DuplicateGroup {
+    size: u64,
+    entries: Vec<LrgEntry>,
+}
+
+impl DuplicateGroup {
+    /// Gets the size of each file in the group, in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Gets the files in the group, in the order they were found.
+    pub fn entries(&self) -> &[LrgEntry] {
+        &self.entries
+    }
+
+    /// Gets the bytes wasted by the copies, which is the size of every file
+    /// after the first.
+    pub fn wasted(&self) -> u64 {
+        self.size * (self.entries.len() as u64 - 1)
+    }
+}
+
+/// The duplicate files found among the entries of an [`Lrg`], along with the
+/// errors encountered while reading them.
+///
+/// [`Lrg`]: struct.Lrg.html
+#[derive(Clone, Debug)]
+pub struct Duplicates {
+    groups: Vec<DuplicateGroup>,
+    errors: Vec<LrgError>,
+}
+
+impl Duplicates {
+    /// Gets the groups of duplicate files, ordered by the bytes they waste
+    /// (largest first).
+    pub fn get_groups(&self) -> Vec<DuplicateGroup> {
+        self.groups.clone()
+    }
+
+    /// Gets the errors encountered while reading files. Files that couldn't be
+    /// read aren't in any group.
+    pub fn get_errors(&self) -> Vec<LrgError> {
+        self.errors.clone()
+    }
+}
+
+/// Groups the files among the entries by their contents. Directories, links
+/// and empty files are skipped, as are other hard links to the same file.
+pub(crate) fn find(entries: &[LrgEntry]) -> Duplicates {
+    let mut errors = Vec::new();
+
+    let files: Vec<LrgEntry> = entries
+        .iter()
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.metadata().is_some_and(|metadata| metadata.len() > 0))
+        .cloned()
+        .collect();
+    let mut groups = split(vec![files], |entry| {
+        entry.metadata().map(|metadata| metadata.len())
+    });
+
+    // Hard links share their contents without wasting anything
+    for group in &mut groups {
+        let mut inodes = HashSet::new();
+        group.retain(|entry| match entry.metadata().and_then(inode) {
+            Some(inode) => inodes.insert(inode),
+            None => true,
+        });
+    }
+    groups.retain(|group| group.len() > 1);
+
+    let groups = split(groups, |entry| {
+        record_error(hash(entry.path(), Some(PARTIAL_SIZE)), entry, &mut errors)
+    });
+    let groups = split(groups, |entry| {
+        // Files no larger than the partial hash were already read in full
+        if entry
+            .metadata()
+            .is_some_and(|metadata| metadata.len() <= PARTIAL_SIZE)
+        {
+            Some(Vec::new())
+        } else {
+            record_error(hash(entry.path(), None), entry, &mut errors)
+        }
+    });
+
+    let mut groups: Vec<DuplicateGroup> = groups
+        .into_iter()
+        .map(|entries| DuplicateGroup {
+            size: entries[0].metadata().map_or(0, |metadata| metadata.len()),
+            entries,
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.wasted()
+            .cmp(&a.wasted())
+            .then_with(|| a.entries[0].path().cmp(b.entries[0].path()))
+    });
+
+    Duplicates { groups, errors }
+}
+
+// Splits each group by a key of its entries, keeping the groups with more than
+// one entry in the order they were found. Entries without a key are dropped
+fn split<K, F>(groups: Vec<Vec<LrgEntry>>, mut key: F) -> Vec<Vec<LrgEntry>>
+where
+    K: Hash + Eq,
+    F: FnMut(&LrgEntry) -> Option<K>,
+{
+    let mut split = Vec::new();
+    for group in groups {
+        let mut index: HashMap<K, usize> = HashMap::new();
+        let mut parts: Vec<Vec<LrgEntry>> = Vec::new();
+        for entry in group {
+            if let Some(key) = key(&entry) {
+                let i = *index.entry(key).or_insert_with(|| {
+                    parts.push(Vec::new());
+                    parts.len() - 1
+                });
+                parts[i].push(entry);
+            }
+        }
+        split.extend(parts.into_iter().filter(|part| part.len() > 1));
+    }
+    split
+}
+
+// Hashes the contents of a file, or only its start if given a limit
+fn hash(path: &Path, limit: Option<u64>) -> io::Result<Vec<u8>> {
+    let file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    match limit {
+        Some(limit) => io::copy(&mut file.take(limit), &mut hasher)?,
+        None => io::copy(&mut &file, &mut hasher)?,
+    };
+    Ok(hasher.finalize().to_vec())
+}
+
+// Keeps the error of a file that couldn't be hashed
+fn record_error<T>(
+    result: io::Result<T>,
+    entry: &LrgEntry,
+    errors: &mut Vec<LrgError>,
+) -> Option<T> {
+    match result {
+        Ok(hash) => Some(hash),
+        Err(err) => {
+            errors.push(LrgError::from_io(entry.path(), &err));
+            None
+        }
+    }
+}
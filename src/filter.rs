@@ -0,0 +1,140 @@
+//! Decides which entries a search skips, based on its [`LrgOptions`].
+//!
+//! [`LrgOptions`]: ../struct.LrgOptions.html
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+
+use crate::{device, DirEntry, LrgError, LrgOptions};
+
+/// The filters of a search, compiled from its options.
+pub(crate) struct Filters {
+    include: Patterns,
+    exclude: Patterns,
+    // The device of the searched path, if the search stays on its file system
+    device: Option<u64>,
+    // Directories on other file systems, which every thread of the walk adds to
+    skipped_mounts: Mutex<Vec<PathBuf>>,
+}
+
+impl Filters {
+    /// Compiles the filters for a search of the path, failing on the first
+    /// invalid pattern.
+    pub(crate) fn new(path: &Path, options: &LrgOptions) -> Result<Filters, LrgError> {
+        let device = if options.same_file_system {
+            fs::metadata(path)
+                .ok()
+                .and_then(|metadata| device(&metadata))
+        } else {
+            None
+        };
+        Ok(Filters {
+            include: Patterns::new(&options.include)?,
+            exclude: Patterns::new(&options.exclude)?,
+            device,
+            skipped_mounts: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Whether the walk should skip the entry (and everything beneath it).
+    /// The searched path itself is never skipped.
+    pub(crate) fn is_excluded(&self, root: &Path, entry: &DirEntry) -> bool {
+        entry.depth() > 0 && self.exclude.matches(root, entry.path())
+    }
+
+    /// Whether the walk should skip a directory (and everything beneath it)
+    /// since it's on another file system than the searched path, noting it if so.
+    pub(crate) fn is_mount_point(&self, entry: &DirEntry) -> bool {
+        let root_device = match self.device {
+            Some(device) if entry.depth() > 0 && entry.file_type().is_dir() => device,
+            _ => return false,
+        };
+        let mount_point = entry
+            .metadata()
+            .ok()
+            .and_then(|metadata| device(&metadata))
+            .is_some_and(|device| device != root_device);
+        if mount_point {
+            // Only a thread that panicked could poison it, which fails the walk
+            let mut skipped = self.skipped_mounts.lock().unwrap();
+            skipped.push(entry.path().to_path_buf());
+        }
+        mount_point
+    }
+
+    /// Takes the mount points skipped while walking, sorted. Threads walking in
+    /// parallel can skip the same mount point, so it's only listed once.
+    pub(crate) fn into_skipped_mounts(self) -> Vec<PathBuf> {
+        let mut skipped = self.skipped_mounts.into_inner().unwrap();
+        skipped.sort();
+        skipped.dedup();
+        skipped
+    }
+
+    /// Whether a file should be searched, ignoring directories since files
+    /// beneath them may still be included.
+    pub(crate) fn is_included(&self, root: &Path, entry: &DirEntry) -> bool {
+        self.include.is_empty()
+            || entry.file_type().is_dir()
+            || self.include.matches(root, entry.path())
+    }
+}
+
+// A list of globs. Globs without a `/` match the file name at any depth, the
+// rest match the whole path relative to the searched path
+struct Patterns {
+    names: GlobSet,
+    paths: GlobSet,
+}
+
+impl Patterns {
+    fn new(patterns: &[String]) -> Result<Patterns, LrgError> {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.trim_end_matches('/');
+            let glob = Self::glob(pattern)?;
+            if pattern.contains('/') {
+                paths.add(glob);
+            } else {
+                names.add(glob);
+            }
+        }
+
+        let build = |builder: GlobSetBuilder| {
+            builder
+                .build()
+                .map_err(|err| LrgError::InvalidPattern(patterns.join(", "), err.to_string()))
+        };
+        Ok(Patterns {
+            names: build(names)?,
+            paths: build(paths)?,
+        })
+    }
+
+    fn glob(pattern: &str) -> Result<Glob, LrgError> {
+        // Leading `/` anchors the pattern at the searched path
+        GlobBuilder::new(pattern.trim_start_matches('/'))
+            .literal_separator(true)
+            .build()
+            .map_err(|err| LrgError::InvalidPattern(pattern.to_owned(), err.kind().to_string()))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.names.is_empty() && self.paths.is_empty()
+    }
+
+    fn matches(&self, root: &Path, path: &Path) -> bool {
+        let name_matches = path
+            .file_name()
+            .is_some_and(|name| self.names.is_match(name));
+        name_matches
+            || (!self.paths.is_empty()
+                && path
+                    .strip_prefix(root)
+                    .is_ok_and(|relative| self.paths.is_match(relative)))
+    }
+}
@@ -0,0 +1,190 @@
+//! Tracks the `.gitignore` and `.ignore` files that apply to each entry of a walk.
+//!
+//! Ignore files are read as their directories are walked (along with the ones in
+//! the parent directories of the searched path, up to its repository), so this
+//! must see every entry in the order it was walked.
+
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use log::warn;
+
+use crate::{absolute_path, DirEntry, Ignored};
+
+/// The ignore files of the directories leading to the current entry of a walk.
+pub(crate) struct IgnoreStack {
+    mode: Ignored,
+    root: PathBuf,
+    // The absolute path of the root, which ignore files match against
+    absolute_root: PathBuf,
+    global: Gitignore,
+    // Directories above the searched path, from the repository down
+    parents: Vec<Frame>,
+    // Directories being walked, from the searched path down
+    frames: Vec<Frame>,
+}
+
+// The ignore files of a directory
+struct Frame {
+    depth: usize,
+    // Whether the directory itself is ignored
+    ignored: bool,
+    ignore: Gitignore,
+    gitignore: Gitignore,
+    // The `.git/info/exclude` file, if the directory is a repository
+    exclude: Gitignore,
+}
+
+impl Frame {
+    fn new(dir: &Path, depth: usize, ignored: bool) -> Frame {
+        let git = dir.join(".git");
+        Frame {
+            depth,
+            ignored,
+            ignore: read_ignore_file(dir, &dir.join(".ignore")),
+            gitignore: read_ignore_file(dir, &dir.join(".gitignore")),
+            exclude: if git.is_dir() {
+                read_ignore_file(dir, &git.join("info").join("exclude"))
+            } else {
+                Gitignore::empty()
+            },
+        }
+    }
+}
+
+// Reads an ignore file (which doesn't need to exist) matching paths in `dir`
+fn read_ignore_file(dir: &Path, path: &Path) -> Gitignore {
+    if !path.is_file() {
+        return Gitignore::empty();
+    }
+    let mut builder = GitignoreBuilder::new(dir);
+    if let Some(err) = builder.add(path) {
+        warn!("Couldn't read ignore file {}: {}", path.display(), err);
+    }
+    builder.build().unwrap_or_else(|err| {
+        warn!("Couldn't read ignore file {}: {}", path.display(), err);
+        Gitignore::empty()
+    })
+}
+
+// Finds the most specific rule matching the path, given the frames of its
+// parent directories. Deeper directories take precedence, `.ignore` files over
+// `.gitignore` files, then the repository's exclude file and finally the global
+// ignore file.
+fn matched(frames: &[&Frame], global: &Gitignore, path: &Path, is_dir: bool) -> bool {
+    for frame in frames.iter().rev() {
+        let matched = frame
+            .ignore
+            .matched(path, is_dir)
+            .or(frame.gitignore.matched(path, is_dir));
+        if !matched.is_none() {
+            return matched.is_ignore();
+        }
+    }
+    for frame in frames.iter().rev() {
+        let matched = frame.exclude.matched(path, is_dir);
+        if !matched.is_none() {
+            return matched.is_ignore();
+        }
+    }
+    global.matched(path, is_dir).is_ignore()
+}
+
+impl IgnoreStack {
+    /// Creates the stack for a walk of `root`, or `None` if ignore files don't
+    /// matter for the mode.
+    pub(crate) fn new(root: &Path, mode: Ignored) -> Option<IgnoreStack> {
+        if mode == Ignored::Include {
+            return None;
+        }
+
+        let absolute_root = absolute_path(root);
+
+        let (global, err) = Gitignore::global();
+        if let Some(err) = err {
+            warn!("Couldn't read global ignore file: {}", err);
+        }
+
+        // Only the parents inside the same repository apply, so none do if the
+        // root is a repository itself
+        let mut parent_dirs: Vec<&Path> = Vec::new();
+        let mut dir = if absolute_root.join(".git").exists() {
+            None
+        } else {
+            absolute_root.parent()
+        };
+        while let Some(parent) = dir {
+            parent_dirs.push(parent);
+            if parent.join(".git").exists() {
+                break;
+            }
+            dir = parent.parent();
+        }
+        if dir.is_none() {
+            parent_dirs.clear();
+        }
+
+        // Parents are ignored if a directory above them ignores them
+        let mut parents: Vec<Frame> = Vec::new();
+        for parent in parent_dirs.into_iter().rev() {
+            let ignored = parents.last().is_some_and(|frame| frame.ignored)
+                || matched(
+                    &parents.iter().collect::<Vec<&Frame>>(),
+                    &global,
+                    parent,
+                    true,
+                );
+            parents.push(Frame::new(parent, 0, ignored));
+        }
+
+        Some(IgnoreStack {
+            mode,
+            root: root.to_path_buf(),
+            absolute_root,
+            global,
+            parents,
+            frames: Vec::new(),
+        })
+    }
+
+    /// Whether the walk should keep the entry. Ignored entries are skipped (or
+    /// are the only files kept), and `.git` directories are always skipped.
+    pub(crate) fn keep(&mut self, entry: &DirEntry) -> bool {
+        while self
+            .frames
+            .last()
+            .is_some_and(|frame| frame.depth >= entry.depth())
+        {
+            self.frames.pop();
+        }
+
+        let is_dir = entry.file_type().is_dir();
+        if is_dir && entry.depth() > 0 && entry.file_name() == ".git" {
+            return false;
+        }
+
+        let path = match entry.path().strip_prefix(&self.root) {
+            Ok(relative) if relative.as_os_str().is_empty() => self.absolute_root.clone(),
+            Ok(relative) => self.absolute_root.join(relative),
+            Err(_) => entry.path().to_path_buf(),
+        };
+        let parent_ignored = self
+            .frames
+            .last()
+            .or_else(|| self.parents.last())
+            .is_some_and(|frame| frame.ignored);
+        let frames: Vec<&Frame> = self.parents.iter().chain(self.frames.iter()).collect();
+        let ignored = parent_ignored || matched(&frames, &self.global, &path, is_dir);
+
+        let keep = match self.mode {
+            Ignored::Include => true,
+            Ignored::Skip => !ignored,
+            // Directories are kept, since files beneath them may be ignored
+            Ignored::Only => is_dir || ignored,
+        };
+        if keep && is_dir {
+            self.frames.push(Frame::new(&path, entry.depth(), ignored));
+        }
+        keep
+    }
+}
@@ -0,0 +1,85 @@
+//! Groups the entries of an [`Lrg`] by a key, such as their extension.
+//!
+//! [`Lrg`]: ../struct.Lrg.html
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{LrgEntry, SortBy};
+
+/// The files of an [`Lrg`] with the same key, along with their total size.
+///
+/// [`Lrg`]: struct.Lrg.html
+#[derive(Clone, Debug)]
+pub struct EntryGroup<K> {
+    key: K,
+    total: u64,
+    count: u64,
+    largest: LrgEntry,
+}
+
+impl<K> EntryGroup<K> {
+    /// Gets the key the files of the group share.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Gets the total size of the files in the group, in bytes.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Gets the number of files in the group.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the largest file in the group. Files of the same size are ordered
+    /// by their path.
+    pub fn largest(&self) -> &LrgEntry {
+        &self.largest
+    }
+}
+
+/// Groups the files among the entries by their key, ordered by their total
+/// size (largest first). Directories are skipped, since their sizes are the
+/// total of the files beneath them.
+pub(crate) fn group<K, F>(entries: &[LrgEntry], mut key: F) -> Vec<EntryGroup<K>>
+where
+    K: Hash + Eq + Clone,
+    F: FnMut(&LrgEntry) -> K,
+{
+    let mut groups: HashMap<K, EntryGroup<K>> = HashMap::new();
+    for entry in entries.iter().filter(|entry| !entry.file_type().is_dir()) {
+        let key = key(entry);
+        match groups.get_mut(&key) {
+            Some(group) => {
+                group.total += entry.size();
+                group.count += 1;
+                if SortBy::Descending.compare(entry, &group.largest) == Ordering::Less {
+                    group.largest = entry.clone();
+                }
+            }
+            None => {
+                let group = EntryGroup {
+                    key: key.clone(),
+                    total: entry.size(),
+                    count: 1,
+                    largest: entry.clone(),
+                };
+                groups.insert(key, group);
+            }
+        }
+    }
+
+    // Groups of the same total are ordered by their largest file, so the order
+    // doesn't depend on the order the entries were found in
+    let mut groups: Vec<EntryGroup<K>> = groups.into_values().collect();
+    groups.sort_by(|a, b| {
+        b.total
+            .cmp(&a.total)
+            .then_with(|| SortBy::Descending.compare(&a.largest, &b.largest))
+    });
+    groups
+}
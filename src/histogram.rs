@@ -0,0 +1,71 @@
+//! Buckets the sizes of the files found by an [`Lrg`] into log-scale bins.
+//!
+//! [`Lrg`]: ../struct.Lrg.html
+
+// The bounds between bins: empty files, then each 10 times the last from 1KiB
+// up to 10GiB
+const BOUNDS: [u64; 9] = [
+    1,
+    1 << 10,
+    10 << 10,
+    100 << 10,
+    1 << 20,
+    10 << 20,
+    100 << 20,
+    1 << 30,
+    10 << 30,
+];
+
+/// A range of file sizes, along with the number and total size of the files
+/// found in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistogramBin {
+    min: u64,
+    max: Option<u64>,
+    count: u64,
+    total: u64,
+}
+
+impl HistogramBin {
+    /// Gets the smallest size in the bin, in bytes.
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// Gets the size the files in the bin are smaller than, in bytes, or `None`
+    /// for the last bin, which has no limit.
+    pub fn max(&self) -> Option<u64> {
+        self.max
+    }
+
+    /// Gets the number of files in the bin.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets the total size of the files in the bin, in bytes.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// Buckets the sizes, which are sorted smallest first, into every bin (even
+/// empty ones), smallest first.
+pub(crate) fn histogram(sizes: &[u64]) -> Vec<HistogramBin> {
+    let mut bins: Vec<HistogramBin> = Vec::with_capacity(BOUNDS.len() + 1);
+    let mut rest = sizes;
+    let mut min = 0;
+    for max in BOUNDS.iter().map(|&max| Some(max)).chain(Some(None)) {
+        let end = max.map_or(rest.len(), |max| rest.partition_point(|&size| size < max));
+        let (bin, after) = rest.split_at(end);
+        bins.push(HistogramBin {
+            min,
+            max,
+            count: bin.len() as u64,
+            total: bin.iter().sum(),
+        });
+        rest = after;
+        min = max.unwrap_or(min);
+    }
+    bins
+}
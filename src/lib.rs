@@ -1,23 +1,36 @@
 /*! 
 `lrg` is a library for find the largest (or smallest) files in a given directory.
-There is also support for searching by a custom function, given [`DirEntry`]'s.
+There is also support for searching by a custom function, given [`LrgEntry`]'s.
 In addition to this, you can specify (in [`LrgOptions`]) the minimum depth and maximum depth to search for,
-such as if you wanted to prevent recursion. You can also speficy whether to follow links or to include
+such as if you wanted to prevent recursion. You can also speficy whether to follow links, glob patterns,
+sizes or modification, access and change times of files to include or exclude, or whether to include
 directories.
 
+Each [`LrgEntry`] holds a [`DirEntry`] along with its size and metadata, which are read once while searching.
+
 Note: [`DirEntry`] is a typedef of [`walkdir::DirEntry`]
 
+Files with the same contents can be found with [`find_duplicates`], and statistics of the sizes of every
+file found (even those not kept by [`new_top_n_with_stats`]) with [`get_stats`] and [`histogram`]. Files can be totalled by their extension with
+[`group_by_extension`], or by any other key with [`group_by`], such as their owner (whose name can be found
+with [`OwnerNames`]). Entries can be arranged into a tree of directories sized by everything beneath them
+with [`tree`], and saved to disk as a [`Snapshot`] to find what changed since. Human-readable sizes (such as
+`10M` for [`min_size`]) can be parsed with [`parse_size`], and durations (such as `90d`) with [`parse_duration`].
+
+Entries can be converted into [`LrgRecord`]'s, which only hold plain data and can be serialized with serde
+(such as to JSON).
+
 ## Examples
 To find the largest files in a directory:
 ```
 use std::path::Path;
-use lrg::{Lrg, LrgOptions, DirEntry, SortBy};
+use lrg::{Lrg, LrgOptions, LrgEntry, SortBy};
 // Get a path to some directory (or file)
 let path = Path::new("./some/path");
 // Create the Lrg object to store the entries
 let mut lrg: Lrg = Lrg::new(path, &LrgOptions::default());
 // Sort and get the entries
-let mut entries: Vec<DirEntry> = lrg.sort_by(&SortBy::Descending).get_entries();
+let mut entries: Vec<LrgEntry> = lrg.sort_by(&SortBy::Descending).get_entries();
 // You can also call `sort_descending`
 entries = lrg.sort_descending().get_entries();
 // These calls mutate the underlying struct, so calling:
@@ -28,44 +41,235 @@ entries = lrg.get_entries();
 To find the smallest files in a directory:
 ```
 # use std::path::Path;
-# use lrg::{Lrg, LrgOptions, DirEntry};
+# use lrg::{Lrg, LrgOptions, LrgEntry};
 let path = Path::new("./some/other/path");
 let mut lrg: Lrg = Lrg::new(path, &LrgOptions::default());
-let entries: Vec<DirEntry> = lrg.sort_ascending().get_entries();
+let entries: Vec<LrgEntry> = lrg.sort_ascending().get_entries();
 ```
 
 To search using a custom function:
 ```
 # use std::path::Path;
-# use lrg::{Lrg, LrgOptions, DirEntry};
+# use lrg::{Lrg, LrgOptions, LrgEntry};
 let path = Path::new("./another/path");
 let mut lrg: Lrg = Lrg::new(path, &LrgOptions::default());
 // Sort by filename (note: not the full path)
-lrg.sort_by_custom(|a: &DirEntry, b: &DirEntry| {
+lrg.sort_by_custom(|a: &LrgEntry, b: &LrgEntry| {
     a.file_name().cmp(b.file_name())
 });
-let entries: Vec<DirEntry> = lrg.get_entries();
+let entries: Vec<LrgEntry> = lrg.get_entries();
+```
+
+Errors encountered while searching don't stop the search, and are collected as [`LrgError`]'s:
+```
+# use std::path::Path;
+# use lrg::{Lrg, LrgOptions};
+let path = Path::new("./another/path");
+let lrg: Lrg = Lrg::new(path, &LrgOptions::default());
+for error in lrg.get_errors() {
+    eprintln!("lrg: {}", error);
+}
 ```
 
 [`Lrg`]: struct.Lrg.html
-[`DirEntry`]: struct.DirEntry.html
+[`DirEntry`]: type.DirEntry.html
+[`LrgEntry`]: struct.LrgEntry.html
 [`LrgOptions`]: struct.LrgOptions.html
+[`LrgError`]: enum.LrgError.html
+[`LrgRecord`]: struct.LrgRecord.html
+[`find_duplicates`]: struct.Lrg.html#method.find_duplicates
+[`new_top_n_with_stats`]: struct.Lrg.html#method.new_top_n_with_stats
+[`get_stats`]: struct.Lrg.html#method.get_stats
+[`histogram`]: struct.Lrg.html#method.histogram
+[`group_by`]: struct.Lrg.html#method.group_by
+[`group_by_extension`]: struct.Lrg.html#method.group_by_extension
+[`OwnerNames`]: struct.OwnerNames.html
+[`tree`]: struct.Lrg.html#method.tree
+[`Snapshot`]: struct.Snapshot.html
+[`min_size`]: struct.LrgOptions.html#structfield.min_size
+[`parse_size`]: fn.parse_size.html
+[`parse_duration`]: fn.parse_duration.html
 [`walkdir::DirEntry`]: https://docs.rs/walkdir/2.2.7/walkdir/struct.DirEntry.html
 */
 
 use std::cmp::Ordering;
-use std::io::ErrorKind;
-use std::path::Path;
+use std::error::Error;
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs::{self, FileType, Metadata};
+use std::hash::Hash;
+use std::io::{self, ErrorKind};
+use std::path::{self, Component, Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
 
-use log::warn;
-use walkdir::WalkDir;
+mod dupes;
+mod filter;
+mod gitignore;
+mod group;
+mod histogram;
+mod names;
+mod parse;
+mod snapshot;
+mod stats;
+mod tree;
+mod walk;
 
-/// Specifies the sorting algorithm.
+pub use crate::dupes::{DuplicateGroup, Duplicates};
+pub use crate::group::EntryGroup;
+pub use crate::histogram::HistogramBin;
+pub use crate::names::OwnerNames;
+pub use crate::parse::{parse_duration, parse_size};
+pub use crate::snapshot::{Change, ChangeKind, Snapshot};
+pub use crate::stats::LrgStats;
+pub use crate::tree::TreeNode;
+
+/// Specifies the sorting algorithm. Entries that are equal by every key of the
+/// sort are ordered by their path, so sorting always gives the same order.
+///
+/// # Examples
+/// To sort by size (largest first), then by modification time (newest first):
+/// ```
+/// # use lrg::{Direction, SortBy, SortKey};
+/// let sort = SortBy::Keys(vec![
+///     (SortKey::Size, Direction::Descending),
+///     (SortKey::Modified, Direction::Descending),
+/// ]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SortBy {
     /// Sorts by filesize ascending
     Ascending,
     /// Sorts by filesize descending
     Descending,
+    /// Sorts by a key of the entries, in either direction
+    Key(SortKey, Direction),
+    /// Sorts by each key in turn, using the later keys for entries that are
+    /// equal by the earlier ones
+    Keys(Vec<(SortKey, Direction)>),
+}
+
+/// Specifies what to sort entries by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    /// The size of the entry
+    Size,
+    /// The time the entry was last modified
+    Modified,
+    /// The time the entry was last accessed
+    Accessed,
+    /// The time the entry's metadata was last changed (only available on unix)
+    Changed,
+    /// The file name of the entry
+    Name,
+    /// The path of the entry
+    Path,
+    /// The depth of the entry, relative to the searched path
+    Depth,
+    /// The extension of the entry's file name, ignoring case
+    Extension,
+}
+
+/// Specifies the direction to sort in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Smallest, earliest or first alphabetically first
+    Ascending,
+    /// Largest, latest or last alphabetically first
+    Descending,
+}
+
+impl SortBy {
+    /// Compares two entries by the sort, then by their path. Entries missing a
+    /// key (such as a time that couldn't be read, or a file name without an
+    /// extension) come after every other entry, whichever the direction.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Direction, Lrg, LrgOptions, SortBy, SortKey};
+    /// let path = Path::new("./some/path");
+    /// let mut lrg = Lrg::new(path, &LrgOptions::default());
+    /// let sort = SortBy::Key(SortKey::Modified, Direction::Descending);
+    /// lrg.sort_by_custom(|a, b| sort.compare(a, b));
+    /// ```
+    pub fn compare(&self, a: &LrgEntry, b: &LrgEntry) -> Ordering {
+        let ordering = match self {
+            SortBy::Ascending => compare_present(a.size, b.size, Direction::Ascending),
+            SortBy::Descending => compare_present(a.size, b.size, Direction::Descending),
+            SortBy::Key(key, direction) => key.compare(a, b, *direction),
+            SortBy::Keys(keys) => keys
+                .iter()
+                .fold(Ordering::Equal, |ordering, (key, direction)| {
+                    ordering.then_with(|| key.compare(a, b, *direction))
+                }),
+        };
+        ordering.then_with(|| a.path().cmp(b.path()))
+    }
+}
+
+impl SortKey {
+    // Compares two entries by the key, with missing keys last
+    fn compare(self, a: &LrgEntry, b: &LrgEntry, direction: Direction) -> Ordering {
+        match self {
+            SortKey::Size => compare_present(a.size, b.size, direction),
+            SortKey::Modified => compare_present(a.modified(), b.modified(), direction),
+            SortKey::Accessed => compare_present(a.accessed(), b.accessed(), direction),
+            SortKey::Changed => compare_present(a.changed(), b.changed(), direction),
+            SortKey::Name => direction.apply(a.file_name().cmp(b.file_name())),
+            SortKey::Path => direction.apply(a.path().cmp(b.path())),
+            SortKey::Depth => direction.apply(a.depth().cmp(&b.depth())),
+            SortKey::Extension => compare_present(a.extension(), b.extension(), direction),
+        }
+    }
+}
+
+impl Direction {
+    // Orders an ascending comparison in the direction
+    fn apply(self, ordering: Ordering) -> Ordering {
+        match self {
+            Direction::Ascending => ordering,
+            Direction::Descending => ordering.reverse(),
+        }
+    }
+}
+
+// Compares keys in the direction, with missing keys last
+fn compare_present<T: Ord>(a: Option<T>, b: Option<T>, direction: Direction) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => direction.apply(a.cmp(&b)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Specifies how the size of a file is measured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeMetric {
+    /// The apparent size of the file, which is how many bytes it holds
+    Apparent,
+    /// The size of the blocks allocated for the file on disk (like `du`), which
+    /// is smaller for sparse files and larger for files smaller than a block.
+    /// Only available on unix, elsewhere it's the apparent size
+    Allocated,
+}
+
+/// Specifies how files ignored by `.gitignore` and `.ignore` files are searched.
+///
+/// Ignore files apply like they do in git: each applies to its own directory and
+/// everything beneath it, along with the ones in the parent directories of the
+/// searched path (up to its repository), `.git/info/exclude` and the global
+/// ignore file. `.git` directories are skipped unless ignored files are included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ignored {
+    /// Searches ignored files like any other file
+    Include,
+    /// Skips ignored files, along with ignored directories and everything beneath them
+    Skip,
+    /// Only searches ignored files
+    Only,
 }
 
 /// Options when constructing an `Lrg` struct.
@@ -73,12 +277,28 @@ pub enum SortBy {
 /// # Examples
 /// Can be constructed like normal:
 /// ```
-/// # use lrg::LrgOptions;
+/// # use lrg::{Ignored, LrgOptions, SizeMetric};
+/// # use std::time::{Duration, SystemTime};
 /// let opts = LrgOptions {
 ///     min_depth: 1,
 ///     max_depth: 5,
 ///     follow_links: false,
 ///     include_dirs: true,
+///     threads: 1,
+///     include: vec!["*.log".to_owned()],
+///     exclude: vec![".git".to_owned(), "node_modules".to_owned()],
+///     ignored: Ignored::Skip,
+///     min_size: 1024,
+///     max_size: u64::MAX,
+///     modified_before: Some(SystemTime::now() - Duration::from_secs(90 * 24 * 60 * 60)),
+///     modified_after: None,
+///     accessed_before: None,
+///     accessed_after: None,
+///     changed_before: None,
+///     changed_after: None,
+///     size_metric: SizeMetric::Allocated,
+///     dedup_links: true,
+///     same_file_system: true,
 /// };
 /// ```
 /// Or can also inherit [`default options`]:
@@ -102,8 +322,57 @@ pub struct LrgOptions {
     pub max_depth: usize,
     /// Specifies whether to follow links while searching
     pub follow_links: bool,
-    /// Speicifies whether to include directories in the search
+    /// Speicifies whether to include directories in the search.
+    /// Directories are sized by the total of every file beneath them (like `du`),
+    /// regardless of `max_depth`
     pub include_dirs: bool,
+    /// Specifies the number of threads to search with. With more than one thread,
+    /// the subdirectories of the searched path are walked in parallel, giving the
+    /// same results as a single thread. Each thread only gets a few thousand
+    /// entries ahead of the subdirectories listed before its own
+    pub threads: usize,
+    /// Specifies glob patterns of the files to search, or every file if empty.
+    /// Patterns without a `/` match the file name at any depth, otherwise they match
+    /// the path relative to the searched path (such as `src/**/*.rs`)
+    pub include: Vec<String>,
+    /// Specifies glob patterns of the files and directories to skip, matched like
+    /// `include`. Skipped directories aren't searched at all
+    pub exclude: Vec<String>,
+    /// Specifies how files ignored by `.gitignore` and `.ignore` files are searched
+    pub ignored: Ignored,
+    /// Specifies the minimum size in bytes of the entries to list
+    pub min_size: u64,
+    /// Specifies the maximum size in bytes of the entries to list. Files outside of
+    /// the sizes still count towards the size of their directories
+    pub max_size: u64,
+    /// Specifies a time the entries to list must have been modified before
+    pub modified_before: Option<SystemTime>,
+    /// Specifies a time the entries to list must have been modified after
+    pub modified_after: Option<SystemTime>,
+    /// Specifies a time the entries to list must have been accessed before
+    pub accessed_before: Option<SystemTime>,
+    /// Specifies a time the entries to list must have been accessed after
+    pub accessed_after: Option<SystemTime>,
+    /// Specifies a time the entries to list must have had their metadata (such
+    /// as permissions or owner) last changed before
+    pub changed_before: Option<SystemTime>,
+    /// Specifies a time the entries to list must have had their metadata last
+    /// changed after. Entries whose times can't be read aren't listed when any
+    /// time is given, so no entries are listed with change times on platforms
+    /// other than unix
+    pub changed_after: Option<SystemTime>,
+    /// Specifies how the sizes of files are measured, which is used for sorting,
+    /// filtering and sizing directories
+    pub size_metric: SizeMetric,
+    /// Specifies whether to list files with several hard links once, with the
+    /// first link found that can be listed (within the depths to list). The
+    /// other links are held by the entry, and don't count towards the size of
+    /// their directories. Only available on unix
+    pub dedup_links: bool,
+    /// Specifies whether to stay on the file system of the searched path, skipping
+    /// directories that are mount points of other file systems (such as `/proc`
+    /// when searching `/`). Only available on unix
+    pub same_file_system: bool,
 }
 
 /// Implements default options
@@ -114,31 +383,614 @@ impl Default for LrgOptions {
     /// ```
     /// use lrg::LrgOptions;
     /// // Gives options that recurse as far as possible, don't follow links,
-    /// // and don't include directories.
+    /// // don't include directories, only use the current thread, and don't filter
+    /// // any files by name, size, time or whether git ignores them.
     /// let options = LrgOptions::default();
     /// ```
     fn default() -> LrgOptions {
         LrgOptions {
             min_depth: 0,
-            max_depth: std::usize::MAX,
+            max_depth: usize::MAX,
             follow_links: false,
             include_dirs: false,
+            threads: 1,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            ignored: Ignored::Include,
+            min_size: 0,
+            max_size: u64::MAX,
+            modified_before: None,
+            modified_after: None,
+            accessed_before: None,
+            accessed_after: None,
+            changed_before: None,
+            changed_after: None,
+            size_metric: SizeMetric::Apparent,
+            dedup_links: false,
+            same_file_system: false,
+        }
+    }
+}
+
+impl LrgOptions {
+    /// Checks whether the entry is within the sizes and times to list, as the
+    /// entries found while searching are. Files whose size couldn't be read
+    /// aren't within any sizes, and entries without metadata aren't within any
+    /// times.
+    ///
+    /// # Examples
+    /// To find every directory, and which of them are large enough to list:
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions};
+    /// let path = Path::new("./some/path");
+    /// let opts = LrgOptions {
+    ///     min_size: 1 << 20,
+    ///     include_dirs: true,
+    ///     ..LrgOptions::default()
+    /// };
+    /// let every = LrgOptions {
+    ///     min_size: 0,
+    ///     ..opts.clone()
+    /// };
+    /// for entry in Lrg::new(path, &every).get_entries() {
+    ///     println!("{}: {}", entry.path().display(), opts.matches_filters(&entry));
+    /// }
+    /// ```
+    pub fn matches_filters(&self, entry: &LrgEntry) -> bool {
+        if (self.min_size, self.max_size) != (0, u64::MAX) {
+            match entry.size {
+                Some(size) if size >= self.min_size && size <= self.max_size => (),
+                _ => return false,
+            }
+        }
+        let modified = (self.modified_after, self.modified_before);
+        let accessed = (self.accessed_after, self.accessed_before);
+        let changed = (self.changed_after, self.changed_before);
+        if [modified, accessed, changed]
+            .iter()
+            .all(|times| *times == (None, None))
+        {
+            return true;
+        }
+        is_between(entry.modified(), modified)
+            && is_between(entry.accessed(), accessed)
+            && is_between(entry.changed(), changed)
+    }
+}
+
+// Whether the time is after and before the given times, if any
+fn is_between(time: Option<SystemTime>, times: (Option<SystemTime>, Option<SystemTime>)) -> bool {
+    match times {
+        (None, None) => true,
+        (after, before) => time.is_some_and(|time| {
+            after.is_none_or(|after| time > after) && before.is_none_or(|before| time < before)
+        }),
+    }
+}
+
+/// An error encountered while searching, along with the offending path.
+///
+/// Errors don't stop a search, instead they are collected and can be retrieved
+/// with [`get_errors`].
+///
+/// # Examples
+/// ```
+/// # use std::path::Path;
+/// # use lrg::{Lrg, LrgOptions, LrgError};
+/// let lrg = Lrg::new(Path::new("./some/path"), &LrgOptions::default());
+/// for error in lrg.get_errors() {
+///     match error {
+///         LrgError::PermissionDenied(path) => println!("skipped {}", path.display()),
+///         other => println!("lrg: {}", other),
+///     }
+/// }
+/// ```
+///
+/// [`get_errors`]: struct.Lrg.html#method.get_errors
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LrgError {
+    /// Permission was denied while opening the path
+    PermissionDenied(PathBuf),
+    /// The path doesn't exist (such as a broken link being followed)
+    NotFound(PathBuf),
+    /// Following a link in `child` led back to its `ancestor`
+    LoopDetected {
+        /// The ancestor directory the link points to
+        ancestor: PathBuf,
+        /// The path of the link
+        child: PathBuf,
+    },
+    /// The metadata of the path couldn't be read
+    Metadata(PathBuf, ErrorKind),
+    /// Any other io error while opening the path
+    Io(PathBuf, ErrorKind),
+    /// A glob pattern in the options couldn't be parsed, along with the reason.
+    /// Nothing is searched when this happens
+    InvalidPattern(String, String),
+}
+
+impl LrgError {
+    /// Gets the path the error occurred at, if the error came from a path.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            LrgError::PermissionDenied(path)
+            | LrgError::NotFound(path)
+            | LrgError::Metadata(path, _)
+            | LrgError::Io(path, _) => Some(path),
+            LrgError::LoopDetected { child, .. } => Some(child),
+            LrgError::InvalidPattern(_, _) => None,
+        }
+    }
+
+    // Converts an error from reading a file
+    fn from_io(path: &Path, err: &io::Error) -> LrgError {
+        let path = path.to_path_buf();
+        match err.kind() {
+            ErrorKind::PermissionDenied => LrgError::PermissionDenied(path),
+            ErrorKind::NotFound => LrgError::NotFound(path),
+            kind => LrgError::Io(path, kind),
+        }
+    }
+
+    // Converts an error from walking, or from getting metadata of an entry
+    fn from_walkdir(err: &walkdir::Error, metadata: bool) -> LrgError {
+        let path = err.path().unwrap_or_else(|| Path::new("")).to_path_buf();
+        if let Some(ancestor) = err.loop_ancestor() {
+            return LrgError::LoopDetected {
+                ancestor: ancestor.to_path_buf(),
+                child: path,
+            };
+        }
+        let kind = err
+            .io_error()
+            .map_or(ErrorKind::Other, |ioerr| ioerr.kind());
+        match kind {
+            ErrorKind::PermissionDenied => LrgError::PermissionDenied(path),
+            ErrorKind::NotFound => LrgError::NotFound(path),
+            _ if metadata => LrgError::Metadata(path, kind),
+            _ => LrgError::Io(path, kind),
         }
     }
 }
 
+impl fmt::Display for LrgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LrgError::PermissionDenied(path) => {
+                write!(f, "error opening '{}': permission denied", path.display())
+            }
+            LrgError::NotFound(path) => {
+                write!(f, "error opening '{}': entity not found", path.display())
+            }
+            LrgError::LoopDetected { ancestor, child } => write!(
+                f,
+                "error opening '{}': link loops back to '{}'",
+                child.display(),
+                ancestor.display()
+            ),
+            LrgError::Metadata(path, kind) => {
+                write!(f, "cannot get metadata of '{}': {}", path.display(), kind)
+            }
+            LrgError::Io(path, kind) => write!(f, "error opening '{}': {}", path.display(), kind),
+            LrgError::InvalidPattern(pattern, reason) => {
+                write!(f, "invalid pattern '{}': {}", pattern, reason)
+            }
+        }
+    }
+}
+
+impl Error for LrgError {}
+
 /// A type copy of the [`walkdir::DirEntry`] struct.
 ///
 /// [`walkdir::DirEntry`]: https://docs.rs/walkdir/2.2.7/walkdir/struct.DirEntry.html
 pub type DirEntry = walkdir::DirEntry;
 
+/// An entry found by [`Lrg`], with its size and metadata read once while searching.
+///
+/// # Examples
+/// ```
+/// # use std::path::Path;
+/// # use lrg::{Lrg, LrgOptions};
+/// let path = Path::new("./some/path");
+/// let mut lrg = Lrg::new(path, &LrgOptions::default());
+/// for entry in lrg.sort_descending().get_entries() {
+///     println!("{}: {}", entry.size(), entry.path().display());
+/// }
+/// ```
+///
+/// [`Lrg`]: struct.Lrg.html
+#[derive(Clone, Debug)]
+pub struct LrgEntry {
+    entry: DirEntry,
+    // Only missing for files whose metadata couldn't be read
+    size: Option<u64>,
+    metadata: Option<Metadata>,
+    // Other hard links to the same file, when links are deduplicated
+    links: Vec<PathBuf>,
+}
+
+impl LrgEntry {
+    // Reads the metadata of an entry, which is only missing if it couldn't be read
+    fn new(entry: DirEntry, metric: SizeMetric, errors: &mut Vec<LrgError>) -> LrgEntry {
+        let metadata = match entry.metadata() {
+            Ok(meta) => Some(meta),
+            Err(err) => {
+                errors.push(LrgError::from_walkdir(&err, true));
+                None
+            }
+        };
+        // Directories are sized by the files beneath them as they're found
+        let size = match &metadata {
+            Some(meta) if !meta.is_dir() => Some(file_size(meta, metric)),
+            None if !entry.file_type().is_dir() => None,
+            _ => Some(0),
+        };
+        LrgEntry {
+            entry,
+            size,
+            metadata,
+            links: Vec::new(),
+        }
+    }
+
+    // An entry that is only needed to know where the walk is, so isn't read
+    fn unread(entry: DirEntry) -> LrgEntry {
+        LrgEntry {
+            entry,
+            size: Some(0),
+            metadata: None,
+            links: Vec::new(),
+        }
+    }
+
+    /// Gets the underlying [`DirEntry`].
+    ///
+    /// [`DirEntry`]: type.DirEntry.html
+    pub fn dir_entry(&self) -> &DirEntry {
+        &self.entry
+    }
+
+    /// Gets the size of the entry. For files this is the file size (measured by
+    /// the options' size metric), for directories it's the total size of every
+    /// file beneath them. Files whose size couldn't be read are 0, see
+    /// [`known_size`].
+    ///
+    /// [`known_size`]: #method.known_size
+    pub fn size(&self) -> u64 {
+        self.size.unwrap_or(0)
+    }
+
+    /// Gets the size of the entry like [`size`], or `None` if it's a file whose
+    /// metadata couldn't be read. Those files are sorted after every other entry.
+    ///
+    /// [`size`]: #method.size
+    pub fn known_size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Gets the metadata read while searching, or `None` if it couldn't be read.
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Gets the number of hard links to the entry, or `None` if its metadata
+    /// couldn't be read (or on platforms without hard link counts).
+    pub fn link_count(&self) -> Option<u64> {
+        self.metadata.as_ref().and_then(link_count)
+    }
+
+    /// Gets the user id of the entry's owner, or `None` if its metadata couldn't
+    /// be read (or on platforms without user ids).
+    pub fn uid(&self) -> Option<u32> {
+        self.metadata.as_ref().and_then(uid)
+    }
+
+    /// Gets the group id of the entry's group, or `None` if its metadata
+    /// couldn't be read (or on platforms without group ids).
+    pub fn gid(&self) -> Option<u32> {
+        self.metadata.as_ref().and_then(gid)
+    }
+
+    /// Gets the paths of the other hard links to the entry that were found while
+    /// searching. Only found when links are deduplicated, otherwise each link is
+    /// its own entry.
+    pub fn links(&self) -> &[PathBuf] {
+        &self.links
+    }
+
+    /// Gets the time the entry was last modified, or `None` if it couldn't be read.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.metadata.as_ref().and_then(|meta| meta.modified().ok())
+    }
+
+    /// Gets the time the entry was last accessed, or `None` if it couldn't be read.
+    pub fn accessed(&self) -> Option<SystemTime> {
+        self.metadata.as_ref().and_then(|meta| meta.accessed().ok())
+    }
+
+    /// Gets the time the entry's metadata was last changed, or `None` if it
+    /// couldn't be read (or on platforms other than unix).
+    pub fn changed(&self) -> Option<SystemTime> {
+        self.metadata.as_ref().and_then(changed)
+    }
+
+    /// Gets the path of the entry.
+    pub fn path(&self) -> &Path {
+        self.entry.path()
+    }
+
+    /// Gets the file name of the entry.
+    pub fn file_name(&self) -> &OsStr {
+        self.entry.file_name()
+    }
+
+    /// Gets the extension of the entry's file name in lowercase, or nothing if
+    /// it doesn't have one (such as `Makefile` or `.bashrc`).
+    pub fn extension(&self) -> Option<String> {
+        Path::new(self.file_name())
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+    }
+
+    /// Gets the file type of the entry.
+    pub fn file_type(&self) -> FileType {
+        self.entry.file_type()
+    }
+
+    /// Gets the depth of the entry, relative to the searched path.
+    pub fn depth(&self) -> usize {
+        self.entry.depth()
+    }
+}
+
+impl From<LrgEntry> for DirEntry {
+    fn from(entry: LrgEntry) -> DirEntry {
+        entry.entry
+    }
+}
+
+/// The type of an [`LrgRecord`].
+///
+/// [`LrgRecord`]: struct.LrgRecord.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryType {
+    /// A regular file
+    File,
+    /// A directory
+    Dir,
+    /// A symbolic link, when links aren't followed
+    Symlink,
+    /// Anything else, such as a socket or device
+    Other,
+}
+
+impl fmt::Display for EntryType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            EntryType::File => "file",
+            EntryType::Dir => "dir",
+            EntryType::Symlink => "symlink",
+            EntryType::Other => "other",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl EntryType {
+    /// Gets the type of a file.
+    pub fn from_file_type(file_type: FileType) -> EntryType {
+        if file_type.is_file() {
+            EntryType::File
+        } else if file_type.is_dir() {
+            EntryType::Dir
+        } else if file_type.is_symlink() {
+            EntryType::Symlink
+        } else {
+            EntryType::Other
+        }
+    }
+}
+
+/// The plain data of an [`LrgEntry`], which can be serialized with serde.
+///
+/// # Examples
+/// ```
+/// # use std::path::Path;
+/// # use lrg::{Lrg, LrgOptions, LrgRecord};
+/// let path = Path::new("./some/path");
+/// let lrg = Lrg::new(path, &LrgOptions::default());
+/// let records: Vec<LrgRecord> = lrg.get_entries().iter().map(LrgRecord::from).collect();
+/// ```
+///
+/// [`LrgEntry`]: struct.LrgEntry.html
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LrgRecord {
+    /// The path of the entry, as it was found while searching
+    pub path: PathBuf,
+    /// The size of the entry, or `None` if it's a file whose size couldn't be
+    /// read, like [`LrgEntry::known_size`]
+    ///
+    /// [`LrgEntry::known_size`]: struct.LrgEntry.html#method.known_size
+    pub size: Option<u64>,
+    /// The type of the entry
+    pub file_type: EntryType,
+    /// When the entry was last modified, in seconds since the unix epoch, or
+    /// `None` if it couldn't be read
+    pub modified: Option<u64>,
+    /// The number of hard links to the entry, or `None` if it couldn't be read
+    pub link_count: Option<u64>,
+    /// The other hard links to the entry, like [`LrgEntry::links`]
+    ///
+    /// [`LrgEntry::links`]: struct.LrgEntry.html#method.links
+    pub links: Vec<PathBuf>,
+}
+
+impl<'a> From<&'a LrgEntry> for LrgRecord {
+    fn from(entry: &'a LrgEntry) -> LrgRecord {
+        let modified = entry
+            .modified()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs());
+        LrgRecord {
+            path: entry.path().to_path_buf(),
+            size: entry.known_size(),
+            file_type: EntryType::from_file_type(entry.file_type()),
+            modified,
+            link_count: entry.link_count(),
+            links: entry.links.clone(),
+        }
+    }
+}
+
+// Measures the size of a file's metadata
+#[cfg(unix)]
+fn file_size(metadata: &Metadata, metric: SizeMetric) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    match metric {
+        SizeMetric::Apparent => metadata.len(),
+        // Blocks are always 512 bytes, whatever the file system's block size
+        SizeMetric::Allocated => metadata.blocks() * 512,
+    }
+}
+
+#[cfg(not(unix))]
+fn file_size(metadata: &Metadata, _metric: SizeMetric) -> u64 {
+    metadata.len()
+}
+
+// Gets the device and inode identifying a file, which its hard links share
+#[cfg(unix)]
+fn inode(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+// Gets the device a file is on, which every file on the same file system shares
+#[cfg(unix)]
+fn device(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn device(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(unix)]
+fn changed(metadata: &Metadata) -> Option<SystemTime> {
+    use std::os::unix::fs::MetadataExt;
+    let since_epoch = Duration::new(
+        metadata.ctime().unsigned_abs(),
+        metadata.ctime_nsec() as u32,
+    );
+    if metadata.ctime() >= 0 {
+        UNIX_EPOCH.checked_add(since_epoch)
+    } else {
+        UNIX_EPOCH.checked_sub(since_epoch)
+    }
+}
+
+#[cfg(not(unix))]
+fn changed(_metadata: &Metadata) -> Option<SystemTime> {
+    None
+}
+
+#[cfg(unix)]
+fn link_count(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.nlink())
+}
+
+#[cfg(not(unix))]
+fn link_count(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(unix)]
+fn uid(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.uid())
+}
+
+#[cfg(not(unix))]
+fn uid(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn gid(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.gid())
+}
+
+#[cfg(not(unix))]
+fn gid(_metadata: &Metadata) -> Option<u32> {
+    None
+}
+
+/// Gets the absolute path of a path, relative to the current directory, without
+/// any `.` components. `..` components are removed along with the directory
+/// before them, unless it's a symbolic link (since its parent is the parent of
+/// where it points to), so the path still leads to the same file.
+///
+/// # Examples
+/// ```
+/// # use std::env;
+/// # use std::path::Path;
+/// use lrg::absolute_path;
+/// let path = absolute_path(Path::new("./some/../path"));
+/// assert_eq!(path, env::current_dir().unwrap().join("path"));
+/// ```
+pub fn absolute_path(path: &Path) -> PathBuf {
+    // An empty path is the current directory
+    let path = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+    let absolute = path::absolute(path).unwrap_or_else(|_err| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                let is_link = fs::symlink_metadata(&normalized)
+                    .is_ok_and(|metadata| metadata.file_type().is_symlink());
+                match normalized.components().next_back() {
+                    Some(Component::Normal(_)) if !is_link => {
+                        normalized.pop();
+                    }
+                    // The parent of the root is the root
+                    Some(Component::RootDir) | Some(Component::Prefix(_)) => (),
+                    _ => normalized.push(component),
+                }
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
 /// The main struct for searching for files by size.
 /// Constructed using [`new`], passing in a path and options.
 ///
 /// [`new`]: struct.Lrg.html#method.new
 #[derive(Clone, Debug)]
 pub struct Lrg {
-    entries: Vec<DirEntry>,
+    entries: Vec<LrgEntry>,
+    errors: Vec<LrgError>,
+    skipped_mounts: Vec<PathBuf>,
+    // The sizes of every file found, if not only those of the entries
+    sizes: Option<Vec<u64>>,
 }
 
 impl Lrg {
@@ -166,32 +1018,132 @@ impl Lrg {
     ///
     /// [`LrgOptions`]: struct.LrgOptions.html
     pub fn new(path: &Path, options: &LrgOptions) -> Self {
-        let mut entries: Vec<DirEntry> = Vec::new();
+        let mut entries: Vec<LrgEntry> = Vec::new();
+        let mut errors: Vec<LrgError> = Vec::new();
 
-        // Walk directory recursivley (prints debug messages if error)
-        for entry in WalkDir::new(&path)
-            .min_depth(options.min_depth)
-            .max_depth(options.max_depth)
-            .follow_links(options.follow_links)
-        {
-            match entry {
-                // Entry can be found
-                Ok(entry) => {
-                    if entry.file_type().is_dir() && options.include_dirs {
-                        entries.push(entry.to_owned())
-                    } else if entry.file_type().is_file() || entry.file_type().is_symlink() {
-                        entries.push(entry.to_owned());
-                    }
-                }
-                Err(err) => {
-                    let path = err.path().unwrap_or_else(|| Path::new("")).display();
-                    let error_message = get_walkdir_error_str(&err);
-                    println!("lrg: error opening '{}': {}", path, error_message);
-                }
+        let skipped_mounts = walk::walk(path, options, &mut errors, |entry| entries.push(entry));
+
+        Lrg {
+            entries,
+            errors,
+            skipped_mounts,
+            sizes: None,
+        }
+    }
+
+    /// Creates a new Lrg holding only the first `n` entries of the given sort,
+    /// already sorted. Unlike calling [`new`] and sorting afterwards, this never
+    /// keeps more than a small multiple of `n` entries in memory while searching.
+    /// Its [`get_stats`] and [`histogram`] only count the entries kept, use
+    /// [`new_top_n_with_stats`] to count every file found.
+    ///
+    /// # Examples
+    /// To get the five largest files:
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions, SortBy};
+    /// let path = Path::new("./some/path");
+    /// let lrg = Lrg::new_top_n(path, &LrgOptions::default(), &SortBy::Descending, 5);
+    /// let entries = lrg.get_entries();
+    /// ```
+    ///
+    /// [`new`]: struct.Lrg.html#method.new
+    /// [`get_stats`]: struct.Lrg.html#method.get_stats
+    /// [`histogram`]: struct.Lrg.html#method.histogram
+    /// [`new_top_n_with_stats`]: struct.Lrg.html#method.new_top_n_with_stats
+    pub fn new_top_n(path: &Path, options: &LrgOptions, sort: &SortBy, n: usize) -> Self {
+        Self::top_n(path, options, sort, n, false)
+    }
+
+    /// Creates a new Lrg holding only the first `n` entries of the given sort,
+    /// like [`new_top_n`], while also keeping the size of every file found for
+    /// [`get_stats`] and [`histogram`]. This keeps a size for each file in
+    /// memory, rather than only the entries kept.
+    ///
+    /// # Examples
+    /// To get the five largest files, and statistics of every file:
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions, SortBy};
+    /// let path = Path::new("./some/path");
+    /// let lrg = Lrg::new_top_n_with_stats(path, &LrgOptions::default(), &SortBy::Descending, 5);
+    /// let stats = lrg.get_stats();
+    /// ```
+    ///
+    /// [`new_top_n`]: struct.Lrg.html#method.new_top_n
+    /// [`get_stats`]: struct.Lrg.html#method.get_stats
+    /// [`histogram`]: struct.Lrg.html#method.histogram
+    pub fn new_top_n_with_stats(
+        path: &Path,
+        options: &LrgOptions,
+        sort: &SortBy,
+        n: usize,
+    ) -> Self {
+        Self::top_n(path, options, sort, n, true)
+    }
+
+    // Searches keeping only the first `n` entries, and the sizes of every file
+    // found if `stats`, otherwise those of the entries kept
+    fn top_n(path: &Path, options: &LrgOptions, sort: &SortBy, n: usize, stats: bool) -> Self {
+        let cmp = |a: &LrgEntry, b: &LrgEntry| sort.compare(a, b);
+        let mut best: Vec<LrgEntry> = Vec::new();
+        let mut errors: Vec<LrgError> = Vec::new();
+        let mut sizes: Vec<u64> = Vec::new();
+
+        let skipped_mounts = walk::walk(path, options, &mut errors, |entry| {
+            if stats {
+                sizes.extend(Self::file_size(&entry));
+            }
+            best.push(entry);
+            // Only prune once the buffer doubles, so pruning stays linear overall
+            if best.len() > n.saturating_mul(2) {
+                Self::keep_first(&mut best, n, cmp);
             }
+        });
+        Self::keep_first(&mut best, n, cmp);
+
+        let mut lrg = Lrg {
+            entries: best,
+            errors,
+            skipped_mounts,
+            sizes: if stats { Some(sizes) } else { None },
+        };
+        lrg.sort_by(sort);
+        lrg
+    }
+
+    // Gets the sizes counted by the stats and histogram, smallest first. They're
+    // only sorted when needed, since searching doesn't need them
+    fn sorted_sizes(&self) -> Vec<u64> {
+        let mut sizes = match &self.sizes {
+            Some(sizes) => sizes.clone(),
+            None => self.entries.iter().filter_map(Self::file_size).collect(),
+        };
+        sizes.sort_unstable();
+        sizes
+    }
+
+    // Gets the size of an entry counted by the stats and histogram, which skip
+    // directories and files whose size couldn't be read
+    fn file_size(entry: &LrgEntry) -> Option<u64> {
+        if entry.file_type().is_dir() {
+            None
+        } else {
+            entry.size
         }
+    }
 
-        Lrg { entries }
+    // Keeps only the first `n` entries of the ordering (in no particular order)
+    fn keep_first<F>(entries: &mut Vec<LrgEntry>, n: usize, cmp: F)
+    where
+        F: FnMut(&LrgEntry, &LrgEntry) -> Ordering,
+    {
+        if entries.len() > n {
+            if n > 0 {
+                entries.select_nth_unstable_by(n - 1, cmp);
+            }
+            entries.truncate(n);
+        }
     }
 
     /// Sorts the lrg object entries, and returns the lrg object.
@@ -205,52 +1157,52 @@ impl Lrg {
     /// let mut lrg = Lrg::new(path, &LrgOptions::default());
     /// lrg.sort_by(&SortBy::Descending);
     /// ```
+    /// To get the most recently modified files first:
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Direction, Lrg, LrgOptions, SortBy, SortKey};
+    /// let path = Path::new(".");
+    /// let mut lrg = Lrg::new(path, &LrgOptions::default());
+    /// lrg.sort_by(&SortBy::Key(SortKey::Modified, Direction::Descending));
+    /// ```
     pub fn sort_by(&mut self, cmp: &SortBy) -> &Self {
         match cmp {
             SortBy::Ascending => self.sort_ascending(),
             SortBy::Descending => self.sort_descending(),
+            SortBy::Key(_, _) | SortBy::Keys(_) => self.sort_by_custom(|a, b| cmp.compare(a, b)),
         }
     }
 
     /// Sorts the lrg object entries by a custom sort function, and returns the lrg object.
     ///
     /// # Examples
-    /// To search by creation date:
+    /// To search by creation date, which isn't a [`SortKey`]:
     /// ```
     /// # use std::path::{Path, PathBuf};
-    /// # use std::ffi::OsStr;
-    /// # use lrg::{Lrg, LrgOptions, DirEntry};
+    /// # use lrg::{Lrg, LrgOptions, LrgEntry};
     /// let path = Path::new("./another/path");
     /// let mut lrg = Lrg::new(path, &LrgOptions::default());
-    /// lrg.sort_by_custom(|a: &DirEntry, b: &DirEntry| {
-    ///     // Create custom function to get creation date of a `DirEntry`
-    ///     let creation_date = |x: &DirEntry| {
-    ///         match x.metadata() {
-    ///             Ok(meta) => {
-    ///                 match meta.created() {
-    ///                     Ok(created) => created,
-    ///                     Err(_) => std::time::SystemTime::UNIX_EPOCH,
-    ///                 }
-    ///             },
-    ///             // Default to UNIX epoch
-    ///             Err(_) => std::time::SystemTime::UNIX_EPOCH,
-    ///         }
-    ///     };
-    ///     //Make comparison
+    /// lrg.sort_by_custom(|a: &LrgEntry, b: &LrgEntry| {
+    ///     // Get the creation date of a `LrgEntry`, if it could be read
+    ///     let creation_date = |x: &LrgEntry| x.metadata().and_then(|meta| meta.created().ok());
+    ///     // Make comparison, putting entries without one first
     ///     creation_date(a).cmp(&creation_date(b))
     /// });
     /// // Get entries
-    /// let entries: Vec<DirEntry> = lrg.get_entries();
+    /// let entries: Vec<LrgEntry> = lrg.get_entries();
     /// ```
+    ///
+    /// [`SortKey`]: enum.SortKey.html
     pub fn sort_by_custom<F>(&mut self, cmp: F) -> &Self
     where
-        F: FnMut(&DirEntry, &DirEntry) -> Ordering,
+        F: FnMut(&LrgEntry, &LrgEntry) -> Ordering,
     {
-        self.entries.sort_unstable_by(cmp);
+        // Stable, so equal entries stay in the order they were found
+        self.entries.sort_by(cmp);
         self
     }
 
-    /// Sorts the lrg object entries by ascending file size, and returns the lrg object.
+    /// Sorts the lrg object entries by ascending file size (then by path), and returns the lrg object.
     ///
     /// # Examples
     /// ```
@@ -261,13 +1213,12 @@ impl Lrg {
     /// let entries = lrg.sort_ascending().get_entries();
     /// ```
     pub fn sort_ascending(&mut self) -> &Self {
-        self.entries.sort_unstable_by(|a: &DirEntry, b: &DirEntry| {
-            Self::get_size(a).cmp(&Self::get_size(b))
-        });
+        self.entries
+            .sort_unstable_by(|a, b| SortBy::Ascending.compare(a, b));
         self
     }
 
-    /// Sorts the lrg object entries by descending file size, and returns the lrg object.
+    /// Sorts the lrg object entries by descending file size (then by path), and returns the lrg object.
     ///
     /// # Examples
     /// ```
@@ -278,26 +1229,11 @@ impl Lrg {
     /// let entries = lrg.sort_descending().get_entries();
     /// ```
     pub fn sort_descending(&mut self) -> &Self {
-        self.entries.sort_unstable_by(|a: &DirEntry, b: &DirEntry| {
-            Self::get_size(b).cmp(&Self::get_size(a))
-        });
+        self.entries
+            .sort_unstable_by(|a, b| SortBy::Descending.compare(a, b));
         self
     }
 
-    fn get_size(entry: &DirEntry) -> u64 {
-        match entry.metadata() {
-            Ok(meta) => meta.len(),
-            Err(err) => {
-                warn!(
-                    "Couldn't get metadata for {}: {:?}",
-                    entry.path().display(),
-                    err
-                );
-                0
-            }
-        }
-    }
-
     /// Gets the entries from the [`Lrg`] object.
     ///
     /// # Examples
@@ -309,40 +1245,180 @@ impl Lrg {
     /// let entries = lrg.sort_ascending().get_entries();
     /// ```
     /// [`Lrg`]: struct.Lrg.html
-    pub fn get_entries(&self) -> Vec<DirEntry> {
+    pub fn get_entries(&self) -> Vec<LrgEntry> {
         self.entries.clone()
     }
-}
 
-/// This function gets a string for a walkdir error.
-/// This is needed since `io_error.to_str()` is not public.
-pub fn get_walkdir_error_str(err: &walkdir::Error) -> String {
-    match err.io_error() {
-        Some(ioerr) => {
-            // Because ErrorKind.as_str() is private, if someone finds a fix, send a pr
-            match ioerr.kind() {
-                ErrorKind::NotFound => "Entity not found".to_owned(),
-                ErrorKind::PermissionDenied => "Permission denied".to_owned(),
-                ErrorKind::ConnectionRefused => "Connection refused".to_owned(),
-                ErrorKind::ConnectionReset => "Connection reset".to_owned(),
-                ErrorKind::ConnectionAborted => "Connection aborted".to_owned(),
-                ErrorKind::NotConnected => "Not connected".to_owned(),
-                ErrorKind::AddrInUse => "Address in use".to_owned(),
-                ErrorKind::AddrNotAvailable => "Address not available".to_owned(),
-                ErrorKind::BrokenPipe => "Broken pipe".to_owned(),
-                ErrorKind::AlreadyExists => "Entity already exists".to_owned(),
-                ErrorKind::WouldBlock => "Operation would block".to_owned(),
-                ErrorKind::InvalidInput => "Invalid input parameter".to_owned(),
-                ErrorKind::InvalidData => "Invalid data".to_owned(),
-                ErrorKind::TimedOut => "Timed out".to_owned(),
-                ErrorKind::WriteZero => "Write zero".to_owned(),
-                ErrorKind::Interrupted => "Operation interrupted".to_owned(),
-                ErrorKind::Other => "Other os error".to_owned(),
-                ErrorKind::UnexpectedEof => "Unexpected end of file".to_owned(),
-                _ => "Unknown error".to_owned(),
-            }
-        }
-        None => "Unknown error".to_owned(),
+    /// Gets the errors encountered while searching.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions};
+    /// let path = Path::new("./another/path");
+    /// let lrg: Lrg = Lrg::new(path, &LrgOptions::default());
+    /// for error in lrg.get_errors() {
+    ///     eprintln!("lrg: {}", error);
+    /// }
+    /// ```
+    pub fn get_errors(&self) -> Vec<LrgError> {
+        self.errors.clone()
+    }
+
+    /// Finds the files among the entries with the same contents, in groups
+    /// ordered by the bytes their copies waste. Files are grouped by size, then
+    /// by a hash of their start and finally by a hash of their whole contents.
+    /// Directories, links, empty files and hard links to the same file aren't
+    /// duplicates.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions};
+    /// let path = Path::new("./some/path");
+    /// let lrg = Lrg::new(path, &LrgOptions::default());
+    /// for group in lrg.find_duplicates().get_groups() {
+    ///     println!("{} copies wasting {} bytes", group.entries().len(), group.wasted());
+    /// }
+    /// ```
+    pub fn find_duplicates(&self) -> Duplicates {
+        dupes::find(&self.entries)
+    }
+
+    /// Groups the files among the entries by a key, totalling their sizes. Groups
+    /// are ordered by their total size (largest first), and directories are
+    /// skipped, since their sizes are the total of the files beneath them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions};
+    /// let path = Path::new("./some/path");
+    /// let lrg = Lrg::new(path, &LrgOptions::default());
+    /// // Group by depth
+    /// for group in lrg.group_by(|entry| entry.depth()) {
+    ///     println!("depth {}: {} bytes", group.key(), group.total());
+    /// }
+    /// ```
+    pub fn group_by<K, F>(&self, key: F) -> Vec<EntryGroup<K>>
+    where
+        K: Hash + Eq + Clone,
+        F: FnMut(&LrgEntry) -> K,
+    {
+        group::group(&self.entries, key)
+    }
+
+    /// Groups the files among the entries by their extension, ignoring case.
+    /// Files without an extension are grouped under `None`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions};
+    /// let path = Path::new("./some/path");
+    /// let lrg = Lrg::new(path, &LrgOptions::default());
+    /// for group in lrg.group_by_extension() {
+    ///     let extension = group.key().as_deref().unwrap_or("(none)");
+    ///     println!("{}: {} files, {} bytes", extension, group.count(), group.total());
+    /// }
+    /// ```
+    pub fn group_by_extension(&self) -> Vec<EntryGroup<Option<String>>> {
+        self.group_by(LrgEntry::extension)
+    }
+
+    /// Arranges the entries beneath the root (usually the path searched in) into
+    /// a tree, with the directories above them. Directories are sized by their
+    /// entry if they were found, or by the total of the entries beneath them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions};
+    /// let path = Path::new("./some/path");
+    /// let opts = LrgOptions {
+    ///     include_dirs: true,
+    ///     ..LrgOptions::default()
+    /// };
+    /// let lrg = Lrg::new(path, &opts);
+    /// let tree = lrg.tree(path);
+    /// for child in tree.children() {
+    ///     println!("{}: {}", child.size(), child.path().display());
+    /// }
+    /// ```
+    pub fn tree(&self, root: &Path) -> TreeNode {
+        TreeNode::new(root, &self.entries)
+    }
+
+    /// Creates a snapshot of the entries beneath the root (usually the path
+    /// searched in), which can be saved and compared with a later scan.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions};
+    /// let path = Path::new("./some/path");
+    /// let opts = LrgOptions {
+    ///     include_dirs: true,
+    ///     ..LrgOptions::default()
+    /// };
+    /// let lrg = Lrg::new(path, &opts);
+    /// lrg.snapshot(path).save(Path::new("scan.lrg")).unwrap();
+    /// ```
+    pub fn snapshot(&self, root: &Path) -> Snapshot {
+        Snapshot::new(root, &self.entries)
+    }
+
+    /// Gets the mount points that were skipped while searching, sorted by path.
+    /// Only mount points skipped by [`same_file_system`] are listed.
+    ///
+    /// [`same_file_system`]: struct.LrgOptions.html#structfield.same_file_system
+    pub fn get_skipped_mounts(&self) -> Vec<PathBuf> {
+        self.skipped_mounts.clone()
+    }
+
+    /// Gets the statistics of the sizes of every file found while searching,
+    /// including those not kept by [`new_top_n_with_stats`] (but only the
+    /// entries kept by [`new_top_n`]). They don't change when the entries are
+    /// sorted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions, SortBy};
+    /// let path = Path::new("./some/path");
+    /// let lrg = Lrg::new_top_n_with_stats(path, &LrgOptions::default(), &SortBy::Descending, 5);
+    /// let stats = lrg.get_stats();
+    /// println!("{} files, {} bytes in total", stats.files, stats.total_bytes);
+    /// ```
+    ///
+    /// [`new_top_n_with_stats`]: struct.Lrg.html#method.new_top_n_with_stats
+    /// [`new_top_n`]: struct.Lrg.html#method.new_top_n
+    pub fn get_stats(&self) -> LrgStats {
+        LrgStats::new(&self.sorted_sizes(), self.errors.len())
+    }
+
+    /// Buckets the sizes of every file found while searching (including those
+    /// not kept by [`new_top_n_with_stats`], but only the entries kept by
+    /// [`new_top_n`]) into log-scale bins, smallest first: empty
+    /// files, then files smaller than 1KiB, 10KiB and so on up to 10GiB, and
+    /// finally larger files. Every bin is returned, even if it's empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions, SortBy};
+    /// let path = Path::new("./some/path");
+    /// // Keep no entries, since only the sizes are needed
+    /// let lrg = Lrg::new_top_n_with_stats(path, &LrgOptions::default(), &SortBy::Descending, 0);
+    /// for bin in lrg.histogram() {
+    ///     println!("{}..{:?}: {} files", bin.min(), bin.max(), bin.count());
+    /// }
+    /// ```
+    ///
+    /// [`new_top_n_with_stats`]: struct.Lrg.html#method.new_top_n_with_stats
+    /// [`new_top_n`]: struct.Lrg.html#method.new_top_n
+    pub fn histogram(&self) -> Vec<HistogramBin> {
+        histogram::histogram(&self.sorted_sizes())
     }
 }
 
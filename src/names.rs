@@ -0,0 +1,77 @@
+//! Resolves the names of the users and groups owning entries.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The names of users and groups by their ids, read from `/etc/passwd` and
+/// `/etc/group`. Users and groups that aren't listed in them (such as those
+/// only known to a directory service) have no name.
+///
+/// # Examples
+/// ```
+/// # use std::path::Path;
+/// # use lrg::{Lrg, LrgOptions, OwnerNames};
+/// let names = OwnerNames::load();
+/// let lrg = Lrg::new(Path::new("./some/path"), &LrgOptions::default());
+/// for entry in lrg.get_entries() {
+///     let owner = entry.uid().and_then(|uid| names.user(uid));
+///     println!("{}: {}", entry.path().display(), owner.unwrap_or("unknown"));
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OwnerNames {
+    users: HashMap<u32, String>,
+    groups: HashMap<u32, String>,
+}
+
+impl OwnerNames {
+    /// Reads the names of users and groups from `/etc/passwd` and `/etc/group`.
+    pub fn load() -> OwnerNames {
+        OwnerNames::from_files(Path::new("/etc/passwd"), Path::new("/etc/group"))
+    }
+
+    /// Reads the names of users and groups from files in the format of
+    /// `/etc/passwd` and `/etc/group`. Files that can't be read are treated as
+    /// empty, so their ids have no names.
+    pub fn from_files(passwd: &Path, group: &Path) -> OwnerNames {
+        OwnerNames {
+            users: read_ids(passwd),
+            groups: read_ids(group),
+        }
+    }
+
+    /// Gets the name of the user with the id.
+    pub fn user(&self, uid: u32) -> Option<&str> {
+        self.users.get(&uid).map(String::as_str)
+    }
+
+    /// Gets the name of the group with the id.
+    pub fn group(&self, gid: u32) -> Option<&str> {
+        self.groups.get(&gid).map(String::as_str)
+    }
+}
+
+// Reads the names by id of a file whose lines start with `name:password:id`,
+// keeping the first name of each id like the system does
+fn read_ids(path: &Path) -> HashMap<u32, String> {
+    let mut names = HashMap::new();
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_err) => return names,
+    };
+    for line in contents.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(':');
+        let name = fields.next();
+        let id = fields.nth(1).and_then(|id| id.parse().ok());
+        if let (Some(name), Some(id)) = (name, id) {
+            if !name.is_empty() {
+                names.entry(id).or_insert_with(|| name.to_string());
+            }
+        }
+    }
+    names
+}
@@ -0,0 +1,98 @@
+//! Parses the human-readable sizes and durations given to lrg, such as the
+//! sizes and ages of the entries to list.
+
+use std::time::Duration;
+
+/// Parses a size such as `10M`, `1.5GiB` or `512k` into bytes, returning `None`
+/// if it isn't a size or doesn't fit in a `u64`.
+///
+/// Sizes are a number (which can have a fraction) followed by an optional
+/// unit, ignoring case and whitespace between them: `B` for bytes, or `K`, `M`,
+/// `G`, `T`, `P` or `E` optionally followed by `B`. Units ending in `iB` (or
+/// `i`) are always binary, so `1KiB` is 1024 bytes. The rest are 1000 bytes
+/// per `K` if `decimal`, otherwise they're 1024 bytes like binary units.
+/// Fractions of a byte are rounded to the nearest byte.
+///
+/// # Examples
+/// ```
+/// use lrg::parse_size;
+/// assert_eq!(parse_size("512k", false), Some(512 * 1024));
+/// assert_eq!(parse_size("512k", true), Some(512 * 1000));
+/// assert_eq!(parse_size("1.5GiB", true), Some(3 << 29));
+/// assert_eq!(parse_size("big", false), None);
+/// ```
+pub fn parse_size(size: &str, decimal: bool) -> Option<u64> {
+    let size = size.trim();
+    let split = size
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let number = number.parse::<f64>().ok()?;
+
+    let unit = unit.trim().to_ascii_lowercase();
+    let (prefix, binary) = if let Some(prefix) = unit.strip_suffix("ib") {
+        (prefix, true)
+    } else if let Some(prefix) = unit.strip_suffix('i') {
+        (prefix, true)
+    } else {
+        (unit.strip_suffix('b').unwrap_or(&unit), !decimal)
+    };
+    let exponent = match prefix {
+        "" => 0,
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        "e" => 6,
+        _ => return None,
+    };
+    // A plain number of bytes can't be binary
+    if exponent == 0 && binary && !unit.is_empty() && unit != "b" {
+        return None;
+    }
+
+    let base: f64 = if binary { 1024.0 } else { 1000.0 };
+    let bytes = (number * base.powi(exponent)).round();
+    // `u64::MAX as f64` rounds up to 2^64, which doesn't fit
+    if bytes.is_finite() && bytes < u64::MAX as f64 {
+        Some(bytes as u64)
+    } else {
+        None
+    }
+}
+
+/// Parses a duration such as `90d`, `12h`, `30m` or `2w`, returning `None` if
+/// it isn't a duration or is too long.
+///
+/// Durations are a number (which can have a fraction) followed by an optional
+/// unit: `s` for seconds, `m` for minutes, `h` for hours, `d` for days, `w` for
+/// weeks or `y` for years of 365 days. Plain numbers are seconds.
+///
+/// # Examples
+/// ```
+/// # use std::time::Duration;
+/// use lrg::parse_duration;
+/// assert_eq!(parse_duration("90d"), Some(Duration::from_secs(90 * 24 * 60 * 60)));
+/// assert_eq!(parse_duration("1.5h"), Some(Duration::from_secs(90 * 60)));
+/// assert_eq!(parse_duration("soon"), None);
+/// ```
+pub fn parse_duration(duration: &str) -> Option<Duration> {
+    let duration = duration.trim();
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(duration.len());
+    let (number, unit) = duration.split_at(split);
+    let number = number.parse::<f64>().ok()?;
+
+    let seconds = match unit.trim() {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        "y" => 365 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(number * seconds as f64).ok()
+}
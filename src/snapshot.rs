@@ -0,0 +1,262 @@
+//! Saves the entries of an [`Lrg`] to disk, so later scans can be compared
+//! with them.
+//!
+//! Snapshots are saved as JSON, marked with their format and version so older
+//! versions of lrg can tell when they can't read a snapshot.
+//!
+//! [`Lrg`]: ../struct.Lrg.html
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{absolute_path, EntryType, LrgEntry, LrgRecord};
+
+// Marks files as snapshots
+const FORMAT: &str = "lrg-scan";
+
+// The version of the snapshot format, increased when it changes so snapshots
+// from newer versions aren't misread
+const VERSION: u32 = 1;
+
+/// The entries found by a scan, with their paths relative to the path searched
+/// in, which can be saved and compared with another scan.
+///
+/// # Examples
+/// ```no_run
+/// # use std::path::Path;
+/// # use lrg::{Lrg, LrgOptions, Snapshot};
+/// let path = Path::new("./some/path");
+/// let lrg = Lrg::new(path, &LrgOptions::default());
+/// lrg.snapshot(path).save(Path::new("yesterday.lrg")).unwrap();
+///
+/// // Later on
+/// let old = Snapshot::load(Path::new("yesterday.lrg")).unwrap();
+/// let new = Lrg::new(path, &LrgOptions::default()).snapshot(path);
+/// for change in old.diff(&new) {
+///     println!("{:?} {}: {} bytes", change.kind(), change.path().display(), change.delta());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    format: String,
+    version: u32,
+    root: PathBuf,
+    created: u64,
+    entries: Vec<LrgRecord>,
+}
+
+impl Snapshot {
+    /// Creates a snapshot of the entries beneath the root, which is usually
+    /// the path searched in. Entries outside of the root are skipped. The root
+    /// is kept as an absolute path, so it can be scanned again from anywhere.
+    pub fn new(root: &Path, entries: &[LrgEntry]) -> Snapshot {
+        let relative = |path: &Path| path.strip_prefix(root).map(Path::to_path_buf).ok();
+        let entries = entries
+            .iter()
+            .filter_map(|entry| {
+                let mut record = LrgRecord::from(entry);
+                record.path = relative(&record.path)?;
+                record.links = record
+                    .links
+                    .iter()
+                    .map(|link| relative(link).unwrap_or_else(|| link.clone()))
+                    .collect();
+                Some(record)
+            })
+            .collect();
+        let created = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Snapshot {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            root: absolute_path(root),
+            created,
+            entries,
+        }
+    }
+
+    /// Reads a snapshot saved with [`save`]. Files that aren't snapshots, or
+    /// are from a newer version of the format, give an error of the kind
+    /// `InvalidData`.
+    ///
+    /// [`save`]: #method.save
+    pub fn load(path: &Path) -> io::Result<Snapshot> {
+        let reader = BufReader::new(File::open(path)?);
+        let value: serde_json::Value = serde_json::from_reader(reader).map_err(|err| {
+            if err.is_io() {
+                io::Error::from(err)
+            } else {
+                io::Error::new(ErrorKind::InvalidData, "not an lrg scan")
+            }
+        })?;
+        if value.get("format").and_then(|format| format.as_str()) != Some(FORMAT) {
+            return Err(io::Error::new(ErrorKind::InvalidData, "not an lrg scan"));
+        }
+        match value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version <= u64::from(VERSION) => {}
+            Some(version) => {
+                let message = format!(
+                    "unsupported scan version {} (only up to {} is supported)",
+                    version, VERSION
+                );
+                return Err(io::Error::new(ErrorKind::InvalidData, message));
+            }
+            None => {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "scan has no version",
+                ))
+            }
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Writes the snapshot to a file, replacing it if it exists.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+
+    /// Gets the absolute path that was searched in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Gets when the snapshot was created, in seconds since the unix epoch.
+    pub fn created(&self) -> u64 {
+        self.created
+    }
+
+    /// Gets the entries of the snapshot, with their paths relative to the root.
+    /// The root itself has an empty path.
+    pub fn entries(&self) -> &[LrgRecord] {
+        &self.entries
+    }
+
+    /// Compares the snapshot with a newer one, by the paths of their entries
+    /// relative to their roots. Entries whose size didn't change aren't
+    /// changes, and neither are entries whose size couldn't be read in either
+    /// snapshot, since it's unknown how they changed. Changes are ordered by how
+    /// many bytes they changed by (most first), then by path.
+    pub fn diff(&self, newer: &Snapshot) -> Vec<Change> {
+        let old: HashMap<&Path, &LrgRecord> = self
+            .entries
+            .iter()
+            .map(|record| (record.path.as_path(), record))
+            .collect();
+        let new_paths: HashSet<&Path> = newer
+            .entries
+            .iter()
+            .map(|record| record.path.as_path())
+            .collect();
+
+        let mut changes: Vec<Change> = Vec::new();
+        for record in &newer.entries {
+            let new_size = match record.size {
+                Some(size) => size,
+                None => continue,
+            };
+            match old.get(record.path.as_path()).map(|old| old.size) {
+                Some(Some(old_size)) if old_size != new_size => {
+                    changes.push(Change::new(record, Some(old_size), Some(new_size)))
+                }
+                None => changes.push(Change::new(record, None, Some(new_size))),
+                _ => (),
+            }
+        }
+        for record in &self.entries {
+            match record.size {
+                Some(old_size) if !new_paths.contains(record.path.as_path()) => {
+                    changes.push(Change::new(record, Some(old_size), None))
+                }
+                _ => (),
+            }
+        }
+
+        changes.sort_by(|a, b| {
+            b.delta()
+                .unsigned_abs()
+                .cmp(&a.delta().unsigned_abs())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        changes
+    }
+}
+
+/// How an entry changed between two snapshots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The entry is only in the newer snapshot
+    Added,
+    /// The entry is only in the older snapshot
+    Removed,
+    /// The entry is larger in the newer snapshot
+    Grown,
+    /// The entry is smaller in the newer snapshot
+    Shrunk,
+}
+
+/// An entry whose size changed between two snapshots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    path: PathBuf,
+    file_type: EntryType,
+    old_size: Option<u64>,
+    new_size: Option<u64>,
+}
+
+impl Change {
+    fn new(record: &LrgRecord, old_size: Option<u64>, new_size: Option<u64>) -> Change {
+        Change {
+            path: record.path.clone(),
+            file_type: record.file_type,
+            old_size,
+            new_size,
+        }
+    }
+
+    /// Gets the path of the entry, relative to the roots of the snapshots.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the type of the entry, in the newer snapshot if it's in both.
+    pub fn file_type(&self) -> EntryType {
+        self.file_type
+    }
+
+    /// Gets how the entry changed.
+    pub fn kind(&self) -> ChangeKind {
+        match (self.old_size, self.new_size) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Removed,
+            (Some(old), Some(new)) if new > old => ChangeKind::Grown,
+            _ => ChangeKind::Shrunk,
+        }
+    }
+
+    /// Gets the size of the entry in the older snapshot, or `None` if it was
+    /// added.
+    pub fn old_size(&self) -> Option<u64> {
+        self.old_size
+    }
+
+    /// Gets the size of the entry in the newer snapshot, or `None` if it was
+    /// removed.
+    pub fn new_size(&self) -> Option<u64> {
+        self.new_size
+    }
+
+    /// Gets how many bytes the entry grew by, which is negative if it shrunk
+    /// or was removed.
+    pub fn delta(&self) -> i64 {
+        self.new_size.unwrap_or(0) as i64 - self.old_size.unwrap_or(0) as i64
+    }
+}
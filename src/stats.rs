@@ -0,0 +1,75 @@
+//! Summary statistics of the files found by an [`Lrg`].
+//!
+//! [`Lrg`]: ../struct.Lrg.html
+
+use serde::{Deserialize, Serialize};
+
+/// Statistics of the sizes of every file found while searching, which aren't
+/// limited to the entries kept by [`new_top_n_with_stats`]. Directories aren't
+/// counted, since their sizes are the total of the files beneath them.
+///
+/// Percentiles use the nearest rank, so they're always the size of a file found.
+/// Every size is 0 if no files were found.
+///
+/// [`new_top_n_with_stats`]: struct.Lrg.html#method.new_top_n_with_stats
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LrgStats {
+    /// The number of files found
+    pub files: u64,
+    /// The total size of the files found
+    pub total_bytes: u64,
+    /// The mean size of the files found
+    pub mean: f64,
+    /// The median size of the files found
+    pub median: u64,
+    /// The size 90% of the files found are no larger than
+    pub p90: u64,
+    /// The size 99% of the files found are no larger than
+    pub p99: u64,
+    /// The size of the largest file found
+    pub largest: u64,
+    /// The size of the smallest file found
+    pub smallest: u64,
+    /// The number of errors encountered while searching
+    pub errors: u64,
+}
+
+impl LrgStats {
+    /// Computes the statistics of the sizes of the files found, which are
+    /// sorted smallest first, and the number of errors encountered finding them.
+    ///
+    /// # Examples
+    /// ```
+    /// use lrg::LrgStats;
+    /// let stats = LrgStats::new(&[10, 20, 30], 0);
+    /// assert_eq!(stats.median, 20);
+    /// ```
+    pub fn new(sizes: &[u64], errors: usize) -> LrgStats {
+        let errors = errors as u64;
+        if sizes.is_empty() {
+            return LrgStats {
+                errors,
+                ..LrgStats::default()
+            };
+        }
+
+        let files = sizes.len() as u64;
+        let total_bytes: u64 = sizes.iter().sum();
+        // The smallest size at least `percent` of the sizes are no larger than
+        let percentile = |percent: u64| {
+            let rank = (percent * files).div_ceil(100).max(1);
+            sizes[rank as usize - 1]
+        };
+        LrgStats {
+            files,
+            total_bytes,
+            mean: total_bytes as f64 / files as f64,
+            median: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            largest: sizes[sizes.len() - 1],
+            smallest: sizes[0],
+            errors,
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Arranges the entries of an [`Lrg`] into a tree of directories, each sized by
+//! everything beneath it.
+//!
+//! [`Lrg`]: ../struct.Lrg.html
+
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+
+use crate::LrgEntry;
+
+/// A file or directory in a tree of entries. Directories that weren't found
+/// themselves (such as when [`include_dirs`] is off) are still in the tree,
+/// without an entry, so every entry is beneath the root.
+///
+/// [`include_dirs`]: struct.LrgOptions.html#structfield.include_dirs
+#[derive(Clone, Debug)]
+pub struct TreeNode {
+    path: PathBuf,
+    size: u64,
+    entry: Option<LrgEntry>,
+    children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    /// Arranges the entries beneath the root into a tree, with the directories
+    /// above them. Entries outside of the root are skipped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::path::Path;
+    /// # use lrg::{Lrg, LrgOptions, TreeNode};
+    /// let path = Path::new("./some/path");
+    /// let lrg = Lrg::new(path, &LrgOptions::default());
+    /// // Only the 5 largest files, and the directories they're in
+    /// let entries: Vec<_> = lrg.get_entries().into_iter().take(5).collect();
+    /// let tree = TreeNode::new(path, &entries);
+    /// ```
+    pub fn new(root: &Path, entries: &[LrgEntry]) -> TreeNode {
+        let mut tree = Builder::default();
+        for entry in entries {
+            let relative = match entry.path().strip_prefix(root) {
+                Ok(relative) => relative,
+                Err(_err) => continue,
+            };
+            let mut node = &mut tree;
+            for component in relative.components() {
+                node = node
+                    .children
+                    .entry(component.as_os_str().to_os_string())
+                    .or_default();
+            }
+            node.entry = Some(entry.clone());
+        }
+        tree.build(root.to_path_buf())
+    }
+
+    /// Gets the path of the node.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Gets the file name of the node, or its whole path if it has none (such
+    /// as for a root of `.` or `/`).
+    pub fn name(&self) -> &OsStr {
+        self.path
+            .file_name()
+            .unwrap_or_else(|| self.path.as_os_str())
+    }
+
+    /// Gets the size of the node, in bytes. Directories that were found are
+    /// sized by their entry, which counts every file beneath them, and other
+    /// directories by the total of their children.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Gets the entry of the node, or `None` if it's a directory that wasn't
+    /// found itself.
+    pub fn entry(&self) -> Option<&LrgEntry> {
+        self.entry.as_ref()
+    }
+
+    /// Gets the children of the node, ordered by size (largest first) and then
+    /// by path.
+    pub fn children(&self) -> &[TreeNode] {
+        &self.children
+    }
+
+    /// Whether the node is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.entry
+            .as_ref()
+            .is_none_or(|entry| entry.file_type().is_dir())
+    }
+
+    /// Finds the node with the path beneath (or at) this one.
+    pub fn find(&self, path: &Path) -> Option<&TreeNode> {
+        let mut node = self;
+        for component in path.strip_prefix(&self.path).ok()?.components() {
+            let path = node.path.join(component);
+            node = node.children.iter().find(|child| child.path == path)?;
+        }
+        Some(node)
+    }
+
+    /// Removes the node with the path beneath this one, such as after deleting
+    /// it, taking its size off of the directories above it.
+    pub fn remove(&mut self, path: &Path) -> Option<TreeNode> {
+        let mut components = path.strip_prefix(&self.path).ok()?.components();
+        let child = self.path.join(components.next()?);
+        let index = self.children.iter().position(|node| node.path == child)?;
+        let removed = if components.next().is_none() {
+            self.children.remove(index)
+        } else {
+            self.children[index].remove(path)?
+        };
+        self.size = self.size.saturating_sub(removed.size);
+        Some(removed)
+    }
+}
+
+// A node whose children are still being found
+#[derive(Default)]
+struct Builder {
+    entry: Option<LrgEntry>,
+    children: HashMap<OsString, Builder>,
+}
+
+impl Builder {
+    // Finishes the node and its children, sizing directories without entries
+    fn build(self, path: PathBuf) -> TreeNode {
+        let mut children: Vec<TreeNode> = self
+            .children
+            .into_iter()
+            .map(|(name, child)| child.build(path.join(name)))
+            .collect();
+        children.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        let size = match &self.entry {
+            Some(entry) => entry.size(),
+            None => children.iter().map(|child| child.size).sum(),
+        };
+        TreeNode {
+            path,
+            size,
+            entry: self.entry,
+            children,
+        }
+    }
+}
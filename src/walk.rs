@@ -0,0 +1,413 @@
+//! Walks a directory for [`Lrg`], either on the current thread or split across
+//! several threads by the search root's subdirectories.
+//!
+//! Both walks produce the same entries and errors in the same order, so sorting
+//! the results afterwards gives identical output.
+//!
+//! [`Lrg`]: ../struct.Lrg.html
+
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::Mutex;
+use std::thread;
+
+use walkdir::{FilterEntry, WalkDir};
+
+use crate::filter::Filters;
+use crate::gitignore::IgnoreStack;
+use crate::{inode, DirEntry, LrgEntry, LrgError, LrgOptions};
+
+// An entry or an error, in the order the walk came across them
+type Walked = Result<LrgEntry, LrgError>;
+
+// Items walked beneath a subdirectory, along with its index in its group
+type Chunk = (usize, Vec<Walked>);
+
+// How many groups of subdirectories to split the walk into, per thread
+const GROUPS_PER_THREAD: usize = 4;
+
+// How many items are sent to the main thread at once, and how many chunks a
+// group can get ahead of it
+const CHUNK_SIZE: usize = 1024;
+const CHUNKS_AHEAD: usize = 4;
+
+/// Walks the path, passing each entry to `found`. Directories are only passed
+/// once everything beneath them has been walked, with their cumulative size.
+/// Returns the mount points that were skipped, in order.
+pub(crate) fn walk<F>(
+    path: &Path,
+    options: &LrgOptions,
+    errors: &mut Vec<LrgError>,
+    found: F,
+) -> Vec<PathBuf>
+where
+    F: FnMut(LrgEntry),
+{
+    let filters = match Filters::new(path, options) {
+        Ok(filters) => filters,
+        Err(err) => {
+            errors.push(err);
+            return Vec::new();
+        }
+    };
+    let mut tracker = Tracker {
+        options,
+        errors,
+        open_dirs: Vec::new(),
+        inodes: HashMap::new(),
+        held: Vec::new(),
+        found,
+    };
+
+    // Splitting only helps if there's something below the root's children
+    if options.threads > 1 && path.is_dir() && (options.max_depth > 1 || options.include_dirs) {
+        walk_parallel(path, options, &filters, &mut tracker);
+    } else {
+        for item in walker(path, options, &filters, usize::MAX, |_| true) {
+            read_item(item, path, options, &filters, &mut |walked| {
+                tracker.visit(walked)
+            });
+        }
+    }
+
+    tracker.finish();
+    filters.into_skipped_mounts()
+}
+
+// Creates the walker every walk uses, so their entries and errors match,
+// skipping excluded or ignored entries (and entries not kept or on other file
+// systems) without descending into them
+fn walker<'a, P>(
+    path: &'a Path,
+    options: &LrgOptions,
+    filters: &'a Filters,
+    max_depth: usize,
+    mut keep: P,
+) -> FilterEntry<walkdir::IntoIter, impl FnMut(&DirEntry) -> bool + 'a>
+where
+    P: FnMut(&DirEntry) -> bool + 'a,
+{
+    // Directory sizes need everything beneath them, so only limit the walk
+    // itself when directories aren't included
+    let max_depth = if options.include_dirs {
+        max_depth
+    } else {
+        max_depth.min(options.max_depth)
+    };
+    let mut ignores = IgnoreStack::new(path, options.ignored);
+    WalkDir::new(path)
+        .follow_links(options.follow_links)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_entry(move |entry| {
+            !filters.is_excluded(path, entry)
+                && ignores.as_mut().is_none_or(|ignores| ignores.keep(entry))
+                && keep(entry)
+                && !filters.is_mount_point(entry)
+        })
+}
+
+// Converts an item of the walk, reading the metadata of the entries that
+// could be listed and dropping files that aren't included
+fn read_item<F>(
+    item: walkdir::Result<DirEntry>,
+    path: &Path,
+    options: &LrgOptions,
+    filters: &Filters,
+    out: &mut F,
+) where
+    F: FnMut(Walked),
+{
+    match item {
+        Ok(entry) if !filters.is_included(path, &entry) => (),
+        Ok(entry) => {
+            let mut errors = Vec::new();
+            let entry = if entry.file_type().is_dir() && !options.include_dirs {
+                // Only needed to know where the walk is
+                LrgEntry::unread(entry)
+            } else {
+                LrgEntry::new(entry, options.size_metric, &mut errors)
+            };
+            for err in errors {
+                out(Err(err));
+            }
+            out(Ok(entry));
+        }
+        Err(err) => out(Err(LrgError::from_walkdir(&err, false))),
+    }
+}
+
+// Walks the root's subdirectories on separate threads, visiting their entries
+// in the order a single walk would have
+fn walk_parallel<F>(path: &Path, options: &LrgOptions, filters: &Filters, tracker: &mut Tracker<F>)
+where
+    F: FnMut(LrgEntry),
+{
+    // The root and its children, in the order the walk lists them
+    let listing: Vec<walkdir::Result<DirEntry>> =
+        walker(path, options, filters, 1, |_| true).collect();
+
+    // Every child directory is walked by a thread, in groups of neighbours
+    let subdirs: Vec<OsString> = listing
+        .iter()
+        .filter_map(|item| item.as_ref().ok())
+        .filter(|entry| entry.depth() == 1 && entry.file_type().is_dir())
+        .map(|entry| entry.file_name().to_owned())
+        .collect();
+    let group_size = subdirs.len() / (options.threads * GROUPS_PER_THREAD) + 1;
+
+    // Each group sends its chunks over its own bounded channel, so threads
+    // walking ahead of the main thread wait instead of holding whole subtrees
+    let mut receivers: Vec<Receiver<Chunk>> = Vec::new();
+    let mut queue: Vec<(&[OsString], SyncSender<Chunk>)> = Vec::new();
+    for group in subdirs.chunks(group_size) {
+        let (sender, receiver) = mpsc::sync_channel(CHUNKS_AHEAD);
+        queue.push((group, sender));
+        receivers.push(receiver);
+    }
+    let queue = Mutex::new(queue.into_iter());
+
+    thread::scope(|scope| {
+        for _ in 0..options.threads {
+            let queue = &queue;
+            scope.spawn(move || loop {
+                // Groups are taken in order, so the group the main thread is
+                // waiting on is always being walked
+                let next = queue.lock().unwrap().next();
+                let (group, sender) = match next {
+                    Some(next) => next,
+                    None => break,
+                };
+                walk_group(path, options, filters, group, &mut |i, walked| {
+                    // The main thread stops listening if it panics
+                    let _ = sender.send((i, walked));
+                });
+            });
+        }
+
+        // Visit the listing in order, waiting on each subdirectory's walk
+        let mut receivers = receivers.into_iter();
+        let mut receiver: Option<Receiver<Chunk>> = None;
+        let mut next: Option<Chunk> = None;
+        let mut subdir = 0;
+        for item in listing {
+            let is_subdir = item
+                .as_ref()
+                .is_ok_and(|entry| entry.depth() == 1 && entry.file_type().is_dir());
+            read_item(item, path, options, filters, &mut |walked| {
+                tracker.visit(walked)
+            });
+            if !is_subdir {
+                continue;
+            }
+
+            if subdir % group_size == 0 {
+                receiver = receivers.next();
+                next = None;
+            }
+            // A subdirectory's chunks end at the next subdirectory's, or when
+            // its group is done. Subdirectories removed since the root was
+            // listed have none
+            let i = subdir % group_size;
+            while let Some((j, walked)) = next
+                .take()
+                .or_else(|| receiver.as_ref().and_then(|receiver| receiver.recv().ok()))
+            {
+                if j != i {
+                    next = Some((j, walked));
+                    break;
+                }
+                for walked in walked {
+                    tracker.visit(walked);
+                }
+            }
+            subdir += 1;
+        }
+    });
+}
+
+// Walks everything beneath a group of the root's subdirectories, passing each
+// subdirectory's items to `out` in chunks, along with its index in the group
+fn walk_group<F>(
+    path: &Path,
+    options: &LrgOptions,
+    filters: &Filters,
+    group: &[OsString],
+    out: &mut F,
+) where
+    F: FnMut(usize, Vec<Walked>),
+{
+    let index: HashMap<&OsStr, usize> = group
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.as_os_str(), i))
+        .collect();
+    // Whether an entry or error at depth 1 belongs to this group
+    let in_group = |path: Option<&Path>| {
+        path.and_then(Path::file_name)
+            .is_some_and(|name| index.contains_key(name))
+    };
+
+    // Walking from the root keeps depths and link loop detection the same as
+    // a single walk, only the listing of the root itself is repeated
+    let it = walker(path, options, filters, usize::MAX, |entry| {
+        entry.depth() != 1 || in_group(Some(entry.path()))
+    });
+    let mut current: Option<(usize, Vec<Walked>)> = None;
+    for item in it {
+        match &item {
+            // The main thread handles the root and its listing
+            Ok(entry) if entry.depth() == 0 => continue,
+            Ok(entry) if entry.depth() == 1 => {
+                if let Some((i, walked)) = current.take() {
+                    out(i, walked);
+                }
+                current = Some((index[entry.file_name()], Vec::new()));
+                continue;
+            }
+            // Errors listing the root show up in every group, but errors
+            // opening a subdirectory only belong to its group
+            Err(err) if err.depth() == 0 || (err.depth() == 1 && !in_group(err.path())) => continue,
+            _ => (),
+        }
+        if let Some((i, walked)) = current.as_mut() {
+            read_item(item, path, options, filters, &mut |item| walked.push(item));
+            if walked.len() >= CHUNK_SIZE {
+                out(*i, mem::take(walked));
+            }
+        }
+    }
+    if let Some((i, walked)) = current.take() {
+        out(i, walked);
+    }
+}
+
+// Tracks the directories being walked, so they can be sized once everything
+// beneath them has been walked
+struct Tracker<'a, F> {
+    options: &'a LrgOptions,
+    errors: &'a mut Vec<LrgError>,
+    // Directories that are still being walked, sized with their running totals
+    open_dirs: Vec<LrgEntry>,
+    // Files found when deduplicating links, by inode
+    inodes: HashMap<(u64, u64), Linked>,
+    // Files with other links, held until the walk finishes to find the links
+    held: Vec<LrgEntry>,
+    found: F,
+}
+
+// A file found when deduplicating links
+enum Linked {
+    // Listed, and held at the index if it has other links
+    Listed(Option<usize>),
+    // Only found at links that weren't listed, kept for the link that is
+    Unlisted(Vec<PathBuf>),
+}
+
+impl<'a, F> Tracker<'a, F>
+where
+    F: FnMut(LrgEntry),
+{
+    fn visit(&mut self, walked: Walked) {
+        let mut entry = match walked {
+            Ok(entry) => entry,
+            Err(err) => {
+                self.errors.push(err);
+                return;
+            }
+        };
+
+        // Close every directory this entry isn't inside of
+        while self
+            .open_dirs
+            .last()
+            .is_some_and(|dir| dir.depth() >= entry.depth())
+        {
+            self.close_dir();
+        }
+
+        if entry.file_type().is_dir() {
+            if self.options.include_dirs && entry.depth() <= self.options.max_depth {
+                self.open_dirs.push(entry);
+            }
+        } else if entry.file_type().is_file() || entry.file_type().is_symlink() {
+            let inode = match entry.metadata() {
+                Some(metadata) if self.options.dedup_links => inode(metadata),
+                _ => None,
+            };
+            let counted = match inode.and_then(|inode| self.inodes.get_mut(&inode)) {
+                Some(Linked::Listed(held)) => {
+                    if let Some(i) = *held {
+                        self.held[i].links.push(entry.path().to_path_buf());
+                    }
+                    return;
+                }
+                Some(Linked::Unlisted(_)) => true,
+                None => false,
+            };
+
+            // Files below the maximum depth only count towards their deepest
+            // listed ancestor, and other links to a file were already counted
+            if !counted {
+                if let Some(dir) = self.open_dirs.last_mut() {
+                    dir.size = Some(dir.size() + entry.size());
+                }
+            }
+            let listed = entry.depth() >= self.options.min_depth
+                && entry.depth() <= self.options.max_depth
+                && self.options.matches_filters(&entry);
+            match inode {
+                Some(inode) if !listed => {
+                    let linked = self
+                        .inodes
+                        .entry(inode)
+                        .or_insert_with(|| Linked::Unlisted(Vec::new()));
+                    if let Linked::Unlisted(links) = linked {
+                        links.push(entry.path().to_path_buf());
+                    }
+                }
+                Some(inode) => {
+                    // Links found before this one weren't listed, so they're
+                    // its links instead
+                    if let Some(Linked::Unlisted(links)) = self.inodes.remove(&inode) {
+                        entry.links = links;
+                    }
+                    if entry.link_count().is_some_and(|count| count > 1) {
+                        self.inodes
+                            .insert(inode, Linked::Listed(Some(self.held.len())));
+                        self.held.push(entry);
+                    } else {
+                        self.inodes.insert(inode, Linked::Listed(None));
+                        (self.found)(entry);
+                    }
+                }
+                None if listed => (self.found)(entry),
+                None => (),
+            }
+        }
+    }
+
+    // Finishes the innermost open directory, adding its total to its parent
+    fn close_dir(&mut self) {
+        if let Some(dir) = self.open_dirs.pop() {
+            if let Some(parent) = self.open_dirs.last_mut() {
+                parent.size = Some(parent.size() + dir.size());
+            }
+            if dir.depth() >= self.options.min_depth && self.options.matches_filters(&dir) {
+                (self.found)(dir);
+            }
+        }
+    }
+
+    fn finish(mut self) {
+        while !self.open_dirs.is_empty() {
+            self.close_dir();
+        }
+        for entry in self.held.drain(..) {
+            (self.found)(entry);
+        }
+    }
+}
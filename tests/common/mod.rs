@@ -0,0 +1,35 @@
+// Helpers shared by the tests
+
+use std::env;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+// A directory in the temp directory, which is removed when dropped, even if the
+// test fails
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    // Creates an empty directory named after the test, so tests running at the
+    // same time don't share directories
+    pub fn new(name: &str) -> TempDir {
+        let dir = env::temp_dir().join(format!("lrg_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,186 @@
+extern crate lrg;
+
+mod common;
+
+// The browser is part of the binary, so it's included from its source
+#[allow(dead_code)]
+#[path = "../src/bin/browser.rs"]
+mod browser;
+
+use std::fs;
+use std::path::Path;
+use crossterm::event::KeyCode;
+use lrg::{Lrg, LrgOptions};
+use browser::{Browser, Sort};
+use common::TempDir;
+
+// Browses the path with the options, as --interactive does
+fn browse(path: &Path, opts: &LrgOptions) -> Browser {
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..opts.clone()
+    };
+    Browser::new(Lrg::new(path, &opts).tree(path))
+}
+
+// Gets the names of the children listed, in the order shown
+fn names(browser: &Browser) -> Vec<String> {
+    browser
+        .children()
+        .iter()
+        .map(|child| child.name().to_string_lossy().into_owned())
+        .collect()
+}
+
+// Presses each key in turn
+fn press(browser: &mut Browser, keys: &[KeyCode]) {
+    for key in keys {
+        assert!(!browser.handle(*key, 10));
+    }
+}
+
+#[test]
+fn test_browser_navigation() {
+    let path = Path::new("tests/testdir");
+    let mut browser = browse(path, &LrgOptions::default());
+    assert_eq!(names(&browser), vec!["somefile", "subdir", "smallerfile", "evensmallerfile"]);
+    assert_eq!(browser.selected(), Some(path.join("somefile")));
+
+    // Movement stays within the children
+    press(&mut browser, &[KeyCode::Up]);
+    assert_eq!(browser.selected, 0);
+    press(&mut browser, &[KeyCode::End]);
+    assert_eq!(browser.selected, 3);
+    press(&mut browser, &[KeyCode::Down, KeyCode::PageDown]);
+    assert_eq!(browser.selected, 3);
+    press(&mut browser, &[KeyCode::Home, KeyCode::Char('j')]);
+    assert_eq!(browser.selected(), Some(path.join("subdir")));
+
+    // Files can't be opened, directories can
+    press(&mut browser, &[KeyCode::Char('k'), KeyCode::Enter]);
+    assert_eq!(browser.current().path(), path);
+    press(&mut browser, &[KeyCode::Down, KeyCode::Enter]);
+    assert_eq!(browser.current().path(), path.join("subdir"));
+    assert_eq!(names(&browser), vec!["subsubdir", "subsomefile", "subsmallerfile", "link_somefile"]);
+    assert_eq!(browser.selected, 0);
+
+    // Going back selects the directory left, and stops at the root
+    press(&mut browser, &[KeyCode::Left]);
+    assert_eq!(browser.current().path(), path);
+    assert_eq!(browser.selected(), Some(path.join("subdir")));
+    press(&mut browser, &[KeyCode::Left]);
+    assert_eq!(browser.current().path(), path);
+
+    assert!(browser.handle(KeyCode::Char('q'), 10));
+}
+
+#[test]
+fn test_browser_sorting() {
+    let path = Path::new("tests/testdir");
+    let mut browser = browse(path, &LrgOptions::default());
+    press(&mut browser, &[KeyCode::Char('j')]);
+
+    // The same child stays selected
+    press(&mut browser, &[KeyCode::Char('n')]);
+    assert_eq!(browser.sort, Sort::Name);
+    assert_eq!(names(&browser), vec!["evensmallerfile", "smallerfile", "somefile", "subdir"]);
+    assert_eq!(browser.selected(), Some(path.join("subdir")));
+
+    press(&mut browser, &[KeyCode::Char('m')]);
+    assert_eq!(browser.sort, Sort::Modified);
+    assert_eq!(names(&browser).len(), 4);
+    assert_eq!(browser.selected(), Some(path.join("subdir")));
+
+    press(&mut browser, &[KeyCode::Char('s')]);
+    assert_eq!(browser.sort, Sort::Size);
+    assert_eq!(names(&browser), vec!["somefile", "subdir", "smallerfile", "evensmallerfile"]);
+    assert_eq!(browser.selected, 1);
+}
+
+#[test]
+fn test_browser_hidden() {
+    let dir = TempDir::new("browser_hidden");
+    fs::write(dir.join(".hidden"), [0; 2048]).unwrap();
+    fs::write(dir.join("shown"), [0; 1024]).unwrap();
+    let mut browser = browse(&dir, &LrgOptions::default());
+    assert_eq!(names(&browser), vec![".hidden", "shown"]);
+
+    press(&mut browser, &[KeyCode::Char('j'), KeyCode::Char('.')]);
+    assert_eq!(names(&browser), vec!["shown"]);
+    assert_eq!(browser.selected(), Some(dir.join("shown")));
+}
+
+#[test]
+fn test_browser_delete() {
+    // Should delete:
+    // browser_delete/
+    // ├── dir/ *
+    // │   ├── inner/ *
+    // │   │   ├── innerfile *
+    // │   ├── dirfile *
+    // ├── marked *
+    // └── kept
+    let dir = TempDir::new("browser_delete");
+    fs::create_dir_all(dir.join("dir/inner")).unwrap();
+    fs::write(dir.join("dir/inner/innerfile"), [0; 4096]).unwrap();
+    fs::write(dir.join("dir/dirfile"), [0; 2048]).unwrap();
+    fs::write(dir.join("marked"), [0; 1024]).unwrap();
+    fs::write(dir.join("kept"), [0; 512]).unwrap();
+    let mut browser = browse(&dir, &LrgOptions::default());
+    assert_eq!(names(&browser), vec!["dir", "marked", "kept"]);
+
+    // Anything but yes keeps the entry
+    press(&mut browser, &[KeyCode::Char('d')]);
+    assert_eq!(browser.pending, vec![dir.join("dir")]);
+    assert_eq!(browser.pending_size(), 6144);
+    press(&mut browser, &[KeyCode::Char('n')]);
+    assert!(browser.pending.is_empty());
+    assert_eq!(browser.message.as_deref(), Some("nothing deleted"));
+    assert!(dir.join("dir").exists());
+
+    // Marked entries are deleted instead of the selected one
+    press(&mut browser, &[KeyCode::Char(' '), KeyCode::Char(' ')]);
+    assert_eq!(browser.marked.len(), 2);
+    assert_eq!(browser.selected(), Some(dir.join("kept")));
+    press(&mut browser, &[KeyCode::Char('d')]);
+    assert_eq!(browser.pending.len(), 2);
+    press(&mut browser, &[KeyCode::Char('y')]);
+    assert_eq!(browser.message.as_deref(), Some("deleted 2 entries"));
+    assert!(browser.marked.is_empty());
+    assert!(!dir.join("dir").exists());
+    assert!(!dir.join("marked").exists());
+    assert!(dir.join("kept").exists());
+    assert_eq!(names(&browser), vec!["kept"]);
+    assert_eq!(browser.current().size(), 512);
+    assert_eq!(browser.selected, 0);
+}
+
+#[test]
+fn test_browser_delete_unscanned() {
+    // Excluded files weren't scanned, so they're kept along with the directories
+    // they're in:
+    // browser_unscanned/
+    // └── dir/ *
+    //     ├── excluded
+    //     └── dirfile *
+    let dir = TempDir::new("browser_unscanned");
+    fs::create_dir(dir.join("dir")).unwrap();
+    fs::write(dir.join("dir/excluded"), [0; 1024]).unwrap();
+    fs::write(dir.join("dir/dirfile"), [0; 1024]).unwrap();
+    let opts = LrgOptions {
+        exclude: vec![String::from("excluded")],
+        ..LrgOptions::default()
+    };
+    let mut browser = browse(&dir, &opts);
+    press(&mut browser, &[KeyCode::Char('d'), KeyCode::Char('Y')]);
+    let message = browser.message.clone().unwrap();
+    assert!(message.starts_with("deleted 0 entries, couldn't delete 1"));
+    assert!(message.ends_with("kept what wasn't scanned beneath it)"));
+    assert!(dir.join("dir/excluded").exists());
+    assert!(!dir.join("dir/dirfile").exists());
+
+    // The directory is still listed, without what was deleted
+    assert_eq!(names(&browser), vec!["dir"]);
+    press(&mut browser, &[KeyCode::Enter]);
+    assert!(names(&browser).is_empty());
+}
@@ -0,0 +1,259 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use common::TempDir;
+
+// Runs lrg in the directory with the arguments
+fn lrg(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_lrg"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_search_dirs_named_like_modes() {
+    // Should list:
+    // clidir/
+    // ├── diff/
+    // │   ├── difffile *
+    // ├── dupe/
+    // │   ├── dupefile *
+    // └── dupes/
+    //     └── dupesfile *
+    let dir = TempDir::new("cli_names");
+    for name in &["diff", "dupe", "dupes"] {
+        fs::create_dir(dir.join(name)).unwrap();
+        fs::write(dir.join(name).join(format!("{}file", name)), [0; 1024]).unwrap();
+    }
+    // Directories named like subcommands are searched as paths or after --
+    let searches: &[(&[&str], &str)] = &[
+        (&["./diff"], "difffile"),
+        (&["dupe"], "dupefile"),
+        (&["./dupes"], "dupesfile"),
+        (&["--", "dupes"], "dupesfile"),
+    ];
+    for (args, file) in searches {
+        let output = lrg(&dir, args);
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert_eq!(stdout.lines().count(), 1);
+        assert!(stdout.ends_with(&format!("{}\n", file)));
+    }
+}
+
+#[test]
+fn test_dupes_and_diff() {
+    let dir = TempDir::new("cli_modes");
+    fs::create_dir(dir.join("dupe")).unwrap();
+    fs::write(dir.join("dupe/somefile"), [1; 1024]).unwrap();
+    fs::write(dir.join("dupe/samefile"), [1; 1024]).unwrap();
+
+    let output = lrg(&dir, &["dupes", "dupe"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("2 x "));
+
+    assert!(lrg(&dir, &["--save", "scan.lrg", "dupe"]).status.success());
+    fs::write(dir.join("dupe/newfile"), [1; 1024]).unwrap();
+    let output = lrg(&dir, &["diff", "scan.lrg"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.lines().any(|line| line.ends_with("newfile (added)")));
+}
+
+#[test]
+fn test_histogram_labels() {
+    // Bins are powers of 1024, even with decimal units
+    let dir = TempDir::new("cli_histogram");
+    fs::write(dir.join("somefile"), [0; 1000]).unwrap();
+    let output = lrg(&dir, &["--histogram", "-u", "decimal"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.lines().any(|line| line.trim_start().starts_with("< 1 KiB |") && line.ends_with("1 file(s), 1 KB")));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_csv_owner_names() {
+    use std::os::unix::fs::MetadataExt;
+    use lrg::OwnerNames;
+    // Owners are named, falling back to their user id
+    let dir = TempDir::new("cli_owner");
+    fs::write(dir.join("somefile"), [0; 1024]).unwrap();
+    let uid = fs::metadata(dir.join("somefile")).unwrap().uid();
+    let names = OwnerNames::load();
+    let owner = names.user(uid).map_or_else(|| uid.to_string(), String::from);
+
+    let output = lrg(&dir, &["-f", "csv", "--columns", "path,owner"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, format!("path,owner\nsomefile,{}\n", owner));
+}
+
+#[test]
+fn test_tree_dir_sizes() {
+    // Directories are sized by every file beneath them, even if they're too
+    // large to list themselves:
+    // testdir/ 1413131
+    // ├── subdir/ 327691
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile
+    // │   ├── link_somefile *
+    // │   ├── subsmallerfile *
+    // │   ├── subsomefile *
+    // ├── evensmallerfile *
+    // ├── smallerfile *
+    // └── somefile
+    let output = lrg(Path::new("tests"), &["--tree", "--max-size", "100K", "-u", "binary", "testdir"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines[0], "testdir (1.35 MiB)");
+    assert_eq!(lines[1], "├── subdir/ (320.01 KiB)");
+    assert_eq!(lines.len(), 7);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unknown_size_shown() {
+    use std::fs::Permissions;
+    use std::os::unix::fs::PermissionsExt;
+    // Files in a directory that can be listed but not searched have an unknown
+    // size, which isn't shown as 0
+    let dir = TempDir::new("cli_unknown_size");
+    fs::create_dir(dir.join("unsearchable")).unwrap();
+    fs::write(dir.join("unsearchable/unreadable"), [0; 1024]).unwrap();
+    fs::write(dir.join("somefile"), [0; 1024]).unwrap();
+    fs::set_permissions(dir.join("unsearchable"), Permissions::from_mode(0o400)).unwrap();
+    let text = lrg(&dir, &["-q"]);
+    let csv = lrg(&dir, &["-q", "-f", "csv", "--columns", "size,human_size,path"]);
+    let readable = fs::metadata(dir.join("unsearchable/unreadable")).is_ok();
+    fs::set_permissions(dir.join("unsearchable"), Permissions::from_mode(0o700)).unwrap();
+    // Root can read it anyway
+    if readable {
+        return;
+    }
+
+    let stdout = String::from_utf8(text.stdout).unwrap();
+    assert_eq!(stdout, "1 KB  somefile\n   ?  unsearchable/unreadable\n");
+    let stdout = String::from_utf8(csv.stdout).unwrap();
+    assert!(stdout.ends_with("1024,1 KB,somefile\n,,unsearchable/unreadable\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_link_counts() {
+    // Link counts are listed whenever a file has several links, aligned like the
+    // sizes, and whether or not they're deduplicated:
+    // cli_links/
+    // ├── dir/
+    // │   └── link *
+    // ├── linked *
+    // └── single
+    let dir = TempDir::new("cli_links");
+    fs::create_dir(dir.join("dir")).unwrap();
+    fs::write(dir.join("linked"), [0; 2048]).unwrap();
+    fs::hard_link(dir.join("linked"), dir.join("dir/link")).unwrap();
+    fs::write(dir.join("single"), [0; 100]).unwrap();
+
+    let output = lrg(&dir, &["-i", "-s", "-size,path"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "4.10 KB     .\n   2 KB     dir\n   2 KB  2  dir/link\n   2 KB  2  linked\n  100 B  1  single\n"
+    );
+    let output = lrg(&dir, &["--dedup-links"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, " 2 KB  2  dir/link (also at: linked)\n100 B  1  single\n");
+
+    // Without several links, there's no count
+    let output = lrg(&dir, &["single"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "100 B  .\n");
+}
+
+// Runs lrg in the directory with the arguments and environment, in a terminal
+// made by script (from util-linux), or None if it isn't installed
+fn lrg_in_terminal(dir: &Path, args: &[&str], env: &[(&str, &str)]) -> Option<String> {
+    let command = format!("{} {}", env!("CARGO_BIN_EXE_lrg"), args.join(" "));
+    let output = Command::new("script")
+        .current_dir(dir)
+        .args(["-qec", &command, "/dev/null"])
+        .env_remove("NO_COLOR")
+        .envs(env.iter().copied())
+        .output()
+        .ok()?;
+    Some(String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn test_color_never_and_piped() {
+    let path = Path::new("tests");
+    for args in [&["testdir"][..], &["--color", "never", "testdir"], &["--color", "auto", "testdir"]] {
+        let output = lrg(path, args);
+        assert!(output.status.success());
+        assert!(!String::from_utf8(output.stdout).unwrap().contains('\x1b'));
+    }
+    if let Some(stdout) = lrg_in_terminal(path, &["--color", "never", "testdir"], &[]) {
+        assert!(stdout.contains("somefile"));
+        assert!(!stdout.contains('\x1b'));
+    }
+}
+
+#[test]
+fn test_color_auto_and_no_color() {
+    // Terminals are colored, unless NO_COLOR is set to anything
+    let path = Path::new("tests");
+    let stdout = match lrg_in_terminal(path, &["testdir"], &[]) {
+        Some(stdout) => stdout,
+        None => return,
+    };
+    assert!(stdout.contains('\x1b'));
+    let stdout = lrg_in_terminal(path, &["testdir"], &[("NO_COLOR", "1")]).unwrap();
+    assert!(stdout.contains("somefile"));
+    assert!(!stdout.contains('\x1b'));
+    let stdout = lrg_in_terminal(path, &["testdir"], &[("NO_COLOR", "")]).unwrap();
+    assert!(stdout.contains('\x1b'));
+}
+
+#[test]
+fn test_color_always() {
+    // Always colors, even when piped or with NO_COLOR set
+    let mut command = Command::new(env!("CARGO_BIN_EXE_lrg"));
+    let output = command
+        .current_dir("tests")
+        .args(["--color", "always", "testdir"])
+        .env("NO_COLOR", "1")
+        .env("LS_COLORS", "di=01;34")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("\x1b[38;5;10m1000 KB\x1b[39m  somefile\n"));
+    assert_eq!(stdout.lines().count(), 5);
+}
+
+#[test]
+fn test_sizes_aligned() {
+    // Sizes are right aligned to the widest, with or without colors
+    let expected = [
+        "1000 KB  somefile",
+        " 200 KB  subdir/subsubdir/subsubsomefile",
+        " 100 KB  subdir/subsomefile",
+        "  50 KB  smallerfile",
+        "  20 KB  subdir/subsmallerfile",
+    ];
+    let output = lrg(Path::new("tests"), &["testdir"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected.join("\n") + "\n");
+
+    let output = lrg(Path::new("tests"), &["--color", "always", "testdir"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let uncolored = stdout.replace("\x1b[38;5;10m", "").replace("\x1b[39m", "");
+    assert_eq!(uncolored, expected.join("\n") + "\n");
+}
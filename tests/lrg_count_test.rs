@@ -1,7 +1,7 @@
 extern crate lrg;
 
 use std::path::Path;
-use lrg::{Lrg, LrgOptions};
+use lrg::{Lrg, LrgOptions, SortBy};
 
 #[test]
 fn test_basic_dir_file_count() {
@@ -125,4 +125,14 @@ fn test_min_depth_count() {
     };
     let lrg = Lrg::new(path, &opts);
     assert_eq!(4, lrg.get_entries().len());
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_top_n_count() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions::default();
+    assert_eq!(0, Lrg::new_top_n(path, &opts, &SortBy::Descending, 0).get_entries().len());
+    assert_eq!(1, Lrg::new_top_n(path, &opts, &SortBy::Descending, 1).get_entries().len());
+    assert_eq!(5, Lrg::new_top_n(path, &opts, &SortBy::Ascending, 5).get_entries().len());
+    assert_eq!(7, Lrg::new_top_n(path, &opts, &SortBy::Descending, 100).get_entries().len());
+}
@@ -0,0 +1,86 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use lrg::{Lrg, LrgEntry, LrgOptions};
+use common::TempDir;
+
+// Gets the sorted file names of the entries
+fn filenames(entries: &[LrgEntry]) -> Vec<String> {
+    let mut names: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+// Creates a directory with duplicate files in the temp directory:
+// dupedir/
+// ├── subdir/
+// │   ├── bigcopy (same as bigfile)
+// ├── bigfile 10000
+// ├── bigsimilar 10000 (same start as bigfile)
+// ├── copy (same as bigfile)
+// ├── emptyfile 0
+// ├── emptycopy 0
+// ├── smallfile 100
+// └── smallcopy (same as smallfile)
+fn create_dupedir(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::create_dir_all(dir.join("subdir")).unwrap();
+    let big: Vec<u8> = (0..10000).map(|i| (i % 251) as u8).collect();
+    let mut similar = big.clone();
+    similar[9999] = 0;
+    fs::write(dir.join("bigfile"), &big).unwrap();
+    fs::write(dir.join("bigsimilar"), &similar).unwrap();
+    fs::write(dir.join("copy"), &big).unwrap();
+    fs::write(dir.join("subdir/bigcopy"), &big).unwrap();
+    fs::write(dir.join("emptyfile"), "").unwrap();
+    fs::write(dir.join("emptycopy"), "").unwrap();
+    fs::write(dir.join("smallfile"), [1; 100]).unwrap();
+    fs::write(dir.join("smallcopy"), [1; 100]).unwrap();
+    dir
+}
+
+#[test]
+fn test_no_duplicates() {
+    let lrg = Lrg::new(&PathBuf::from("tests/testdir"), &LrgOptions::default());
+    let duplicates = lrg.find_duplicates();
+    assert!(duplicates.get_groups().is_empty());
+    assert!(duplicates.get_errors().is_empty());
+}
+
+#[test]
+fn test_duplicate_groups() {
+    let dir = create_dupedir("duplicate_groups");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let duplicates = Lrg::new(&dir, &opts).find_duplicates();
+
+    let groups = duplicates.get_groups();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(filenames(groups[0].entries()), vec!["bigcopy", "bigfile", "copy"]);
+    assert_eq!(groups[0].size(), 10000);
+    assert_eq!(groups[0].wasted(), 20000);
+    assert_eq!(filenames(groups[1].entries()), vec!["smallcopy", "smallfile"]);
+    assert_eq!(groups[1].wasted(), 100);
+    assert!(duplicates.get_errors().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_hard_links_not_duplicates() {
+    let dir = create_dupedir("hard_links_not_duplicates");
+    fs::remove_file(dir.join("smallcopy")).unwrap();
+    fs::hard_link(dir.join("smallfile"), dir.join("smalllink")).unwrap();
+    let duplicates = Lrg::new(&dir, &LrgOptions::default()).find_duplicates();
+
+    let groups = duplicates.get_groups();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].entries().len(), 3);
+}
@@ -0,0 +1,31 @@
+extern crate lrg;
+
+use std::path::{Path, PathBuf};
+use lrg::{Lrg, LrgOptions, LrgError};
+
+#[test]
+fn test_no_errors() {
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new(path, &LrgOptions::default());
+    assert!(lrg.get_errors().is_empty());
+}
+
+#[test]
+fn test_not_found_error() {
+    let path = Path::new("tests/nonexistentdir");
+    let lrg = Lrg::new(path, &LrgOptions::default());
+    assert!(lrg.get_entries().is_empty());
+    assert_eq!(
+        lrg.get_errors(),
+        vec![LrgError::NotFound(PathBuf::from("tests/nonexistentdir"))]
+    );
+}
+
+#[test]
+fn test_error_path() {
+    let error = LrgError::LoopDetected {
+        ancestor: PathBuf::from("a"),
+        child: PathBuf::from("a/b/link"),
+    };
+    assert_eq!(error.path(), Some(Path::new("a/b/link")));
+}
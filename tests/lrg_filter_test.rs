@@ -0,0 +1,335 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs::{self, File};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use lrg::{Lrg, LrgOptions, LrgError, LrgEntry};
+use common::TempDir;
+
+// Creates a vector of strings from strs
+macro_rules! vec_of_strings {
+    ($($x:expr),*) => (vec![$($x.to_string()),*]);
+}
+
+// Gets the sorted file names of the entries
+fn filenames(entries: &[LrgEntry]) -> Vec<String> {
+    let mut names: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_exclude_dir() {
+    // Should count:
+    // testdir/
+    // ├── subdir/ (excluded)
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile
+    // │   ├── link_somefile
+    // │   ├── subsmallerfile
+    // │   ├── subsomefile
+    // ├── evensmallerfile *
+    // ├── smallerfile *
+    // └── somefile *
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        exclude: vec_of_strings!["subdir"],
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings!["evensmallerfile", "smallerfile", "somefile"]
+    );
+}
+
+#[test]
+fn test_exclude_dir_size() {
+    // Should count:
+    // subdir/ 1
+    // ├── subsubdir/ (excluded)
+    // │   ├── subsubsomefile
+    // ├── link_somefile 4
+    // ├── subsmallerfile 3
+    // └── subsomefile 2
+    let path = Path::new("tests/testdir/subdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        exclude: vec_of_strings!["subsubdir/"],
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(path, &opts);
+    let sizes: Vec<u64> = lrg.sort_descending().get_entries().iter().map(|entry| entry.size()).collect();
+    assert_eq!(sizes, vec![122891, 102400, 20480, 11]);
+}
+
+#[test]
+fn test_include_name() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile *
+    // │   ├── link_somefile *
+    // │   ├── subsmallerfile
+    // │   ├── subsomefile *
+    // ├── evensmallerfile
+    // ├── smallerfile
+    // └── somefile *
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include: vec_of_strings!["*somefile"],
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings!["link_somefile", "somefile", "subsomefile", "subsubsomefile"]
+    );
+}
+
+#[test]
+fn test_include_relative_path() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile
+    // │   ├── link_somefile *
+    // │   ├── subsmallerfile *
+    // │   ├── subsomefile *
+    // ├── evensmallerfile
+    // ├── smallerfile
+    // └── somefile
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include: vec_of_strings!["subdir/*"],
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings!["link_somefile", "subsmallerfile", "subsomefile"]
+    );
+}
+
+#[test]
+fn test_include_and_exclude() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include: vec_of_strings!["**/sub*"],
+        exclude: vec_of_strings!["subsub*"],
+        threads: 2,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings!["subsmallerfile", "subsomefile"]
+    );
+}
+
+#[test]
+fn test_invalid_pattern() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        exclude: vec_of_strings!["a[b"],
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert!(lrg.get_entries().is_empty());
+    match lrg.get_errors().as_slice() {
+        [LrgError::InvalidPattern(pattern, _)] => assert_eq!(pattern, "a[b"),
+        errors => panic!("unexpected errors: {:?}", errors),
+    }
+}
+
+#[test]
+fn test_min_and_max_size() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile *
+    // │   ├── link_somefile
+    // │   ├── subsmallerfile *
+    // │   ├── subsomefile *
+    // ├── evensmallerfile
+    // ├── smallerfile *
+    // └── somefile
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        min_size: 20480,
+        max_size: 204800,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings!["smallerfile", "subsmallerfile", "subsomefile", "subsubsomefile"]
+    );
+}
+
+#[test]
+fn test_min_size_dir() {
+    // Should count:
+    // testdir/ *
+    // ├── subdir/ *
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile
+    // │   ├── link_somefile
+    // │   ├── subsmallerfile
+    // │   ├── subsomefile
+    // ├── evensmallerfile
+    // ├── smallerfile
+    // └── somefile *
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        min_size: 300000,
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(path, &opts);
+    let sizes: Vec<u64> = lrg.sort_descending().get_entries().iter().map(|entry| entry.size()).collect();
+    // Directories still count the files too small to list
+    assert_eq!(sizes, vec![1413131, 1024000, 327691]);
+}
+
+#[test]
+fn test_matches_filters() {
+    // The entries matching the filters are the ones a filtered search finds
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        min_size: 20480,
+        max_size: 204800,
+        ..LrgOptions::default()
+    };
+    let every = Lrg::new(path, &LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    });
+    let matching: Vec<LrgEntry> = every
+        .get_entries()
+        .into_iter()
+        .filter(|entry| opts.matches_filters(entry))
+        .collect();
+    assert_eq!(filenames(&matching), filenames(&Lrg::new(path, &opts).get_entries()));
+    assert_eq!(
+        filenames(&matching),
+        vec_of_strings!["smallerfile", "subsmallerfile", "subsomefile", "subsubdir", "subsubsomefile"]
+    );
+}
+
+#[test]
+fn test_modified_times() {
+    // Should count:
+    // timedir/
+    // ├── newfile
+    // └── oldfile (modified 100 days ago)
+    let dir = TempDir::new("timedir");
+    let day = Duration::from_secs(24 * 60 * 60);
+    File::create(dir.join("newfile")).unwrap();
+    File::create(dir.join("oldfile"))
+        .unwrap()
+        .set_modified(SystemTime::now() - day * 100)
+        .unwrap();
+
+    let older = LrgOptions {
+        modified_before: Some(SystemTime::now() - day * 90),
+        ..LrgOptions::default()
+    };
+    assert_eq!(filenames(&Lrg::new(&dir, &older).get_entries()), vec_of_strings!["oldfile"]);
+    let newer = LrgOptions {
+        modified_after: Some(SystemTime::now() - day),
+        ..LrgOptions::default()
+    };
+    assert_eq!(filenames(&Lrg::new(&dir, &newer).get_entries()), vec_of_strings!["newfile"]);
+    let between = LrgOptions {
+        modified_before: Some(SystemTime::now() - day),
+        modified_after: Some(SystemTime::now() - day * 90),
+        ..LrgOptions::default()
+    };
+    assert!(Lrg::new(&dir, &between).get_entries().is_empty());
+}
+
+#[test]
+fn test_accessed_times() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        accessed_after: Some(SystemTime::now() + Duration::from_secs(60 * 60)),
+        ..LrgOptions::default()
+    };
+    assert!(Lrg::new(path, &opts).get_entries().is_empty());
+    let opts = LrgOptions {
+        accessed_before: Some(SystemTime::now() + Duration::from_secs(60 * 60)),
+        ..LrgOptions::default()
+    };
+    assert_eq!(Lrg::new(path, &opts).get_entries().len(), 7);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_changed_times() {
+    let dir = TempDir::new("changedir");
+    let day = Duration::from_secs(24 * 60 * 60);
+    // Setting the modification time changes the metadata, so the file is
+    // still newly changed
+    File::create(dir.join("changedfile"))
+        .unwrap()
+        .set_modified(SystemTime::now() - day * 100)
+        .unwrap();
+
+    let newer = LrgOptions {
+        changed_after: Some(SystemTime::now() - day),
+        ..LrgOptions::default()
+    };
+    assert_eq!(filenames(&Lrg::new(&dir, &newer).get_entries()), vec_of_strings!["changedfile"]);
+    let older = LrgOptions {
+        changed_before: Some(SystemTime::now() - day),
+        ..LrgOptions::default()
+    };
+    assert!(Lrg::new(&dir, &older).get_entries().is_empty());
+    let modified = LrgOptions {
+        modified_before: Some(SystemTime::now() - day),
+        changed_after: Some(SystemTime::now() - day),
+        ..LrgOptions::default()
+    };
+    assert_eq!(filenames(&Lrg::new(&dir, &modified).get_entries()), vec_of_strings!["changedfile"]);
+}
+
+#[test]
+fn test_same_file_system() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        same_file_system: true,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert_eq!(lrg.get_entries().len(), 7);
+    assert!(lrg.get_skipped_mounts().is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_same_file_system_skips_mounts() {
+    use std::os::unix::fs::MetadataExt;
+    // /proc is its own file system, unless it isn't mounted
+    let root = fs::metadata("/").unwrap().dev();
+    if fs::metadata("/proc").map_or(true, |metadata| metadata.dev() == root) {
+        return;
+    }
+    let opts = LrgOptions {
+        max_depth: 1,
+        same_file_system: true,
+        threads: 2,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(Path::new("/"), &opts);
+    assert!(lrg.get_skipped_mounts().contains(&Path::new("/proc").to_path_buf()));
+}
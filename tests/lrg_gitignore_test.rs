@@ -0,0 +1,126 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use lrg::{Ignored, Lrg, LrgOptions, LrgEntry};
+use common::TempDir;
+
+// Creates a vector of strings from strs
+macro_rules! vec_of_strings {
+    ($($x:expr),*) => (vec![$($x.to_string()),*]);
+}
+
+// Gets the sorted file names of the entries
+fn filenames(entries: &[LrgEntry]) -> Vec<String> {
+    let mut names: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+// Creates a directory with ignore files in the temp directory, which isn't
+// inside of a repository:
+// ignoredir/
+// ├── .gitignore (*.log, build/)
+// ├── build/
+// │   ├── output
+// ├── sub/
+// │   ├── .ignore (!important.log)
+// │   ├── debug.log
+// │   ├── important.log
+// ├── app.log
+// └── main.rs
+fn create_ignoredir(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::create_dir_all(dir.join("build")).unwrap();
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join(".gitignore"), "*.log\nbuild/\n").unwrap();
+    fs::write(dir.join("build/output"), [0; 4096]).unwrap();
+    fs::write(dir.join("sub/.ignore"), "!important.log\n").unwrap();
+    fs::write(dir.join("sub/debug.log"), [0; 2048]).unwrap();
+    fs::write(dir.join("sub/important.log"), [0; 1024]).unwrap();
+    fs::write(dir.join("app.log"), [0; 512]).unwrap();
+    fs::write(dir.join("main.rs"), [0; 256]).unwrap();
+    dir
+}
+
+#[test]
+fn test_include_ignored() {
+    let dir = create_ignoredir("include_ignored");
+    let lrg = Lrg::new(&dir, &LrgOptions::default());
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings![".gitignore", ".ignore", "app.log", "debug.log", "important.log", "main.rs", "output"]
+    );
+}
+
+#[test]
+fn test_respect_gitignore() {
+    let dir = create_ignoredir("respect_gitignore");
+    let opts = LrgOptions {
+        ignored: Ignored::Skip,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(&dir, &opts);
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings![".gitignore", ".ignore", "important.log", "main.rs"]
+    );
+}
+
+#[test]
+fn test_respect_gitignore_dir_size() {
+    let dir = create_ignoredir("respect_gitignore_dir_size");
+    let opts = LrgOptions {
+        max_depth: 0,
+        include_dirs: true,
+        ignored: Ignored::Skip,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(&dir, &opts);
+    let entries = lrg.get_entries();
+    assert_eq!(entries.len(), 1);
+    // .gitignore, .ignore, important.log and main.rs
+    assert_eq!(entries[0].size(), 13 + 15 + 1024 + 256);
+}
+
+#[test]
+fn test_only_ignored() {
+    let dir = create_ignoredir("only_ignored");
+    let opts = LrgOptions {
+        ignored: Ignored::Only,
+        threads: 2,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(&dir, &opts);
+    assert_eq!(
+        filenames(&lrg.get_entries()),
+        vec_of_strings!["app.log", "debug.log", "output"]
+    );
+}
+
+#[test]
+fn test_respect_gitignore_nested_repository() {
+    // The outer repository's ignore files don't apply inside of another one:
+    // outer/
+    // ├── .git/
+    // ├── .gitignore (*.bin)
+    // └── inner/
+    //     ├── .git/
+    //     └── a.bin
+    let dir = TempDir::new("nested_repository");
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    fs::create_dir_all(dir.join("inner/.git")).unwrap();
+    fs::write(dir.join(".gitignore"), "*.bin\n").unwrap();
+    fs::write(dir.join("inner/a.bin"), [0; 1024]).unwrap();
+
+    let opts = LrgOptions {
+        ignored: Ignored::Skip,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(&dir.join("inner"), &opts);
+    assert_eq!(filenames(&lrg.get_entries()), vec_of_strings!["a.bin"]);
+}
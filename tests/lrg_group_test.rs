@@ -0,0 +1,98 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use lrg::{Lrg, LrgOptions};
+use common::TempDir;
+
+// Creates a directory with files of several extensions in the temp directory:
+// groupdir/
+// ├── sub/
+// │   ├── notes.TXT 2048
+// │   ├── photo.jpg 4096
+// ├── .bashrc 64
+// ├── Makefile 128
+// ├── photo.JPG 1024
+// ├── readme.txt 512
+// └── todo.txt 2048
+fn create_groupdir(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("sub/notes.TXT"), [0; 2048]).unwrap();
+    fs::write(dir.join("sub/photo.jpg"), [0; 4096]).unwrap();
+    fs::write(dir.join(".bashrc"), [0; 64]).unwrap();
+    fs::write(dir.join("Makefile"), [0; 128]).unwrap();
+    fs::write(dir.join("photo.JPG"), [0; 1024]).unwrap();
+    fs::write(dir.join("readme.txt"), [0; 512]).unwrap();
+    fs::write(dir.join("todo.txt"), [0; 2048]).unwrap();
+    dir
+}
+
+#[test]
+fn test_group_by_extension() {
+    let dir = create_groupdir("group_by_extension");
+    let lrg = Lrg::new(&dir, &LrgOptions::default());
+    let groups = lrg.group_by_extension();
+    let totals: Vec<(Option<String>, u64, u64)> = groups
+        .iter()
+        .map(|group| (group.key().clone(), group.total(), group.count()))
+        .collect();
+    assert_eq!(
+        totals,
+        vec![
+            (Some("jpg".to_string()), 5120, 2),
+            (Some("txt".to_string()), 4608, 3),
+            (None, 192, 2),
+        ]
+    );
+    assert_eq!(groups[0].largest().path(), dir.join("sub/photo.jpg"));
+    // Files of the same size are ordered by path
+    assert_eq!(groups[1].largest().path(), dir.join("sub/notes.TXT"));
+    assert_eq!(groups[2].largest().path(), dir.join("Makefile"));
+}
+
+#[test]
+fn test_group_by_skips_dirs() {
+    let dir = create_groupdir("group_by_skips_dirs");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(&dir, &opts);
+    let groups = lrg.group_by(|_| ());
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].total(), 5120 + 4608 + 192);
+    assert_eq!(groups[0].count(), 7);
+}
+
+#[test]
+fn test_group_by_custom() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile 204800
+    // │   ├── link_somefile 11
+    // │   ├── subsmallerfile 20480
+    // │   ├── subsomefile 102400
+    // ├── evensmallerfile 10240
+    // ├── smallerfile 51200
+    // └── somefile 1024000
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new(path, &LrgOptions::default());
+    let totals: Vec<(usize, u64)> = lrg
+        .group_by(|entry| entry.depth())
+        .iter()
+        .map(|group| (*group.key(), group.total()))
+        .collect();
+    assert_eq!(totals, vec![(1, 1085440), (3, 204800), (2, 122891)]);
+}
+
+#[test]
+fn test_group_by_empty() {
+    let dir = TempDir::new("group_by_empty");
+    let lrg = Lrg::new(&dir, &LrgOptions::default());
+    assert!(lrg.group_by_extension().is_empty());
+}
@@ -0,0 +1,67 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use lrg::{Lrg, LrgOptions, SortBy};
+use common::TempDir;
+
+#[test]
+fn test_histogram() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile 204800
+    // │   ├── link_somefile 11
+    // │   ├── subsmallerfile 20480
+    // │   ├── subsomefile 102400
+    // ├── evensmallerfile 10240
+    // ├── smallerfile 51200
+    // └── somefile 1024000
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new(path, &LrgOptions::default());
+    let bins: Vec<(u64, Option<u64>, u64, u64)> = lrg
+        .histogram()
+        .iter()
+        .map(|bin| (bin.min(), bin.max(), bin.count(), bin.total()))
+        .collect();
+    assert_eq!(
+        bins,
+        vec![
+            (0, Some(1), 0, 0),
+            (1, Some(1 << 10), 1, 11),
+            (1 << 10, Some(10 << 10), 0, 0),
+            (10 << 10, Some(100 << 10), 3, 10240 + 20480 + 51200),
+            (100 << 10, Some(1 << 20), 3, 102400 + 204800 + 1024000),
+            (1 << 20, Some(10 << 20), 0, 0),
+            (10 << 20, Some(100 << 20), 0, 0),
+            (100 << 20, Some(1 << 30), 0, 0),
+            (1 << 30, Some(10 << 30), 0, 0),
+            (10 << 30, None, 0, 0),
+        ]
+    );
+}
+
+#[test]
+fn test_histogram_top_n() {
+    // Every file found is counted, even if none are kept
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new_top_n_with_stats(path, &LrgOptions::default(), &SortBy::Descending, 0);
+    assert!(lrg.get_entries().is_empty());
+    assert_eq!(lrg.histogram(), Lrg::new(path, &LrgOptions::default()).histogram());
+}
+
+#[test]
+fn test_histogram_empty_files() {
+    let dir = TempDir::new("histogram_empty_files");
+    fs::write(dir.join("empty"), []).unwrap();
+    fs::write(dir.join("kilobyte"), [0; 1024]).unwrap();
+    let bins = Lrg::new(&dir, &LrgOptions::default()).histogram();
+    assert_eq!(bins.len(), 10);
+    assert_eq!(bins[0].count(), 1);
+    assert_eq!(bins[1].count(), 0);
+    assert_eq!(bins[2].count(), 1);
+    assert_eq!(bins[2].total(), 1024);
+}
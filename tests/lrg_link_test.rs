@@ -0,0 +1,116 @@
+#![cfg(unix)]
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use lrg::{Lrg, LrgOptions};
+use common::TempDir;
+
+// Creates a directory with hard links in the temp directory:
+// linkdir/
+// ├── subdir/
+// │   ├── otherlink (same as somefile)
+// ├── somefile 4096
+// ├── somelink (same as somefile)
+// └── smallfile 1024
+fn create_linkdir(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::create_dir_all(dir.join("subdir")).unwrap();
+    fs::write(dir.join("somefile"), [0; 4096]).unwrap();
+    fs::hard_link(dir.join("somefile"), dir.join("somelink")).unwrap();
+    fs::hard_link(dir.join("somefile"), dir.join("subdir/otherlink")).unwrap();
+    fs::write(dir.join("smallfile"), [0; 1024]).unwrap();
+    dir
+}
+
+#[test]
+fn test_links_listed_separately() {
+    let dir = create_linkdir("links_listed");
+    let opts = LrgOptions {
+        max_depth: 0,
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let entries = Lrg::new(&dir, &opts).get_entries();
+    assert_eq!(entries[0].size(), 3 * 4096 + 1024);
+}
+
+#[test]
+fn test_dedup_links() {
+    let dir = create_linkdir("dedup_links");
+    for threads in 1..3 {
+        let opts = LrgOptions {
+            threads,
+            dedup_links: true,
+            ..LrgOptions::default()
+        };
+        let mut lrg = Lrg::new(&dir, &opts);
+        let entries = lrg.sort_descending().get_entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].size(), 4096);
+        assert_eq!(entries[0].link_count(), Some(3));
+        assert_eq!(entries[1].link_count(), Some(1));
+        assert!(entries[1].links().is_empty());
+
+        // Every link is listed once, whichever was found first
+        let mut paths: Vec<PathBuf> = entries[0].links().to_vec();
+        paths.push(entries[0].path().to_path_buf());
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![dir.join("somefile"), dir.join("somelink"), dir.join("subdir/otherlink")]
+        );
+    }
+}
+
+#[test]
+fn test_dedup_links_dir_size() {
+    let dir = create_linkdir("dedup_links_dir_size");
+    let opts = LrgOptions {
+        max_depth: 0,
+        include_dirs: true,
+        dedup_links: true,
+        ..LrgOptions::default()
+    };
+    let entries = Lrg::new(&dir, &opts).get_entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].size(), 4096 + 1024);
+}
+
+#[test]
+fn test_dedup_links_unlisted_first() {
+    // Each file is linked from below the maximum depth, and whichever link is
+    // found first, the one above it is still listed:
+    // deepdir/
+    // ├── d0/b/deep (same as f0)
+    // ├── ...
+    // ├── f0 1024
+    // └── ...
+    let dir = TempDir::new("dedup_links_unlisted");
+    for i in 0..8 {
+        let deep = dir.join(format!("d{}/b", i));
+        fs::create_dir_all(&deep).unwrap();
+        fs::write(dir.join(format!("f{}", i)), [0; 1024]).unwrap();
+        fs::hard_link(dir.join(format!("f{}", i)), deep.join("deep")).unwrap();
+    }
+    let opts = LrgOptions {
+        max_depth: 1,
+        include_dirs: true,
+        dedup_links: true,
+        ..LrgOptions::default()
+    };
+    let entries = Lrg::new(&dir, &opts).get_entries();
+    for i in 0..8 {
+        let file = entries
+            .iter()
+            .find(|entry| entry.path() == dir.join(format!("f{}", i)))
+            .unwrap();
+        assert_eq!(file.link_count(), Some(2));
+        assert!(file.links().iter().all(|link| *link == dir.join(format!("d{}/b/deep", i))));
+    }
+    // Each file is still counted once
+    let root = entries.iter().find(|entry| entry.path() == &*dir).unwrap();
+    assert_eq!(root.size(), 8 * 1024);
+}
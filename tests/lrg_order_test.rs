@@ -1,15 +1,21 @@
 extern crate lrg;
 
+mod common;
+
+use std::fs::File;
 use std::path::Path;
 use std::ffi::OsStr;
-use lrg::{Lrg, LrgOptions, DirEntry};
+use std::time::{Duration, SystemTime};
+use lrg::{Direction, Lrg, LrgOptions, LrgEntry, SortBy, SortKey};
+use common::TempDir;
 
 // Creates a vector of strings from strs
 macro_rules! vec_of_strings {
     ($($x:expr),*) => (vec![$($x.to_string()),*]);
 }
 
-fn test_entries_against_filenames(entries: &Vec<DirEntry>, filenames: &Vec<String>) {
+#[allow(clippy::ptr_arg)]
+fn test_entries_against_filenames(entries: &Vec<LrgEntry>, filenames: &Vec<String>) {
     assert_eq!(entries.len(), filenames.len());
     for (i, entry) in entries.iter().enumerate() {
         assert_eq!(entry.path().file_name().expect("Cannot get filename"), OsStr::new(&filenames[i]));
@@ -104,16 +110,16 @@ fn test_basic_max_depth_file_order() {
 #[test]
 fn test_basic_file_and_dir_order() {
     // Should count:
-    // testdir/ 7
-    // ├── subdir/ 8
-    // │   ├── subsubdir/ 9
-    // │   │   ├── subsubsomefile 2
+    // testdir/ 1
+    // ├── subdir/ 3
+    // │   ├── subsubdir/ 4
+    // │   │   ├── subsubsomefile 4
     // │   ├── link_somefile 10
-    // │   ├── subsmallerfile 5
-    // │   ├── subsomefile 3
-    // ├── evensmallerfile 6
-    // ├── smallerfile 4
-    // └── somefile 1
+    // │   ├── subsmallerfile 8
+    // │   ├── subsomefile 6
+    // ├── evensmallerfile 9
+    // ├── smallerfile 7
+    // └── somefile 2
     let path = Path::new("tests/testdir");
     let opts = LrgOptions {
         include_dirs: true,
@@ -121,18 +127,54 @@ fn test_basic_file_and_dir_order() {
     };
     let mut lrg = Lrg::new(path, &opts);
     let entries = lrg.sort_descending().get_entries();
+    // Custom call since subsubdir will be same size as its only file
+    let fourth_filename = entries[3].path().file_name().expect("Cannot get filename");
+    let (fourth, fifth) = if fourth_filename == OsStr::new("subsubdir") {
+        ("subsubdir", "subsubsomefile")
+    } else {
+        ("subsubsomefile", "subsubdir")
+    };
     test_entries_against_filenames(
-        &entries, 
+        &entries,
         &vec_of_strings![
+            "testdir",
             "somefile",
-            "subsubsomefile",
+            "subdir",
+            fourth,
+            fifth,
             "subsomefile",
             "smallerfile",
             "subsmallerfile",
             "evensmallerfile",
-            "testdir",
+            "link_somefile"
+        ]
+    );
+}
+
+#[test]
+fn test_subdir_file_and_dir_max_depth_order() {
+    // Should count:
+    // subdir/ 1
+    // ├── subsubdir/ 2
+    // │   ├── subsubsomefile
+    // ├── link_somefile 5
+    // ├── subsmallerfile 4
+    // └── subsomefile 3
+    let path = Path::new("tests/testdir/subdir");
+    let opts = LrgOptions {
+        max_depth: 1,
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(path, &opts);
+    let entries = lrg.sort_descending().get_entries();
+    test_entries_against_filenames(
+        &entries,
+        &vec_of_strings![
             "subdir",
             "subsubdir",
+            "subsomefile",
+            "subsmallerfile",
             "link_somefile"
         ]
     );
@@ -218,4 +260,273 @@ fn test_min_depth_order() {
             "link_somefile"
         ]
     );
-}
\ No newline at end of file
+}
+#[test]
+fn test_top_n_descending_order() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile 2
+    // │   ├── link_somefile
+    // │   ├── subsmallerfile
+    // │   ├── subsomefile 3
+    // ├── evensmallerfile
+    // ├── smallerfile
+    // └── somefile 1
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new_top_n(path, &LrgOptions::default(), &SortBy::Descending, 3);
+    test_entries_against_filenames(
+        &lrg.get_entries(),
+        &vec_of_strings![
+            "somefile",
+            "subsubsomefile",
+            "subsomefile"
+        ]
+    );
+}
+
+#[test]
+fn test_top_n_ascending_order() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile
+    // │   ├── link_somefile 1
+    // │   ├── subsmallerfile 3
+    // │   ├── subsomefile
+    // ├── evensmallerfile 2
+    // ├── smallerfile
+    // └── somefile
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new_top_n(path, &LrgOptions::default(), &SortBy::Ascending, 3);
+    test_entries_against_filenames(
+        &lrg.get_entries(),
+        &vec_of_strings![
+            "link_somefile",
+            "evensmallerfile",
+            "subsmallerfile"
+        ]
+    );
+}
+
+#[test]
+fn test_top_n_matches_sorted_order() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(path, &opts);
+    let sorted = lrg.sort_descending().get_entries();
+    let top = Lrg::new_top_n(path, &opts, &SortBy::Descending, 3);
+    assert_eq!(
+        top.get_entries().iter().map(|entry| entry.size()).collect::<Vec<u64>>(),
+        sorted[..3].iter().map(|entry| entry.size()).collect::<Vec<u64>>()
+    );
+}
+
+#[test]
+fn test_name_order() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile 7
+    // │   ├── link_somefile 2
+    // │   ├── subsmallerfile 5
+    // │   ├── subsomefile 6
+    // ├── evensmallerfile 1
+    // ├── smallerfile 3
+    // └── somefile 4
+    let path = Path::new("tests/testdir");
+    let mut lrg = Lrg::new(path, &LrgOptions::default());
+    let entries = lrg.sort_by(&SortBy::Key(SortKey::Name, Direction::Ascending)).get_entries();
+    test_entries_against_filenames(
+        &entries,
+        &vec_of_strings![
+            "evensmallerfile",
+            "link_somefile",
+            "smallerfile",
+            "somefile",
+            "subsmallerfile",
+            "subsomefile",
+            "subsubsomefile"
+        ]
+    );
+}
+
+#[test]
+fn test_top_n_depth_order() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile 1
+    // │   ├── link_somefile
+    // │   ├── subsmallerfile
+    // │   ├── subsomefile
+    // ├── evensmallerfile
+    // ├── smallerfile
+    // └── somefile
+    let path = Path::new("tests/testdir");
+    let sort = SortBy::Key(SortKey::Depth, Direction::Descending);
+    let lrg = Lrg::new_top_n(path, &LrgOptions::default(), &sort, 1);
+    test_entries_against_filenames(&lrg.get_entries(), &vec_of_strings!["subsubsomefile"]);
+}
+
+#[test]
+fn test_modified_and_extension_order() {
+    // Should count:
+    // sortdir/
+    // ├── archive.TAR 2 (modified 2 days ago)
+    // ├── notes.md 1 (modified 1 day ago)
+    // └── README 3 (modified 3 days ago)
+    let dir = TempDir::new("sortdir");
+    let day = Duration::from_secs(24 * 60 * 60);
+    for (i, name) in ["notes.md", "archive.TAR", "README"].iter().enumerate() {
+        File::create(dir.join(name))
+            .unwrap()
+            .set_modified(SystemTime::now() - day * (i as u32 + 1))
+            .unwrap();
+    }
+
+    let mut lrg = Lrg::new(&dir, &LrgOptions::default());
+    let entries = lrg.sort_by(&SortBy::Key(SortKey::Modified, Direction::Descending)).get_entries();
+    test_entries_against_filenames(&entries, &vec_of_strings!["notes.md", "archive.TAR", "README"]);
+    let entries = lrg.sort_by(&SortBy::Key(SortKey::Modified, Direction::Ascending)).get_entries();
+    test_entries_against_filenames(&entries, &vec_of_strings!["README", "archive.TAR", "notes.md"]);
+
+    // Files without an extension come last either way
+    let entries = lrg.sort_by(&SortBy::Key(SortKey::Extension, Direction::Ascending)).get_entries();
+    test_entries_against_filenames(&entries, &vec_of_strings!["notes.md", "archive.TAR", "README"]);
+    let entries = lrg.sort_by(&SortBy::Key(SortKey::Extension, Direction::Descending)).get_entries();
+    test_entries_against_filenames(&entries, &vec_of_strings!["archive.TAR", "notes.md", "README"]);
+}
+
+#[test]
+fn test_size_ties_by_path_order() {
+    // Should count:
+    // testdir/ 1
+    // ├── subdir/ 3
+    // │   ├── subsubdir/ 4
+    // │   │   ├── subsubsomefile 5
+    // │   ├── link_somefile 11
+    // │   ├── subsmallerfile 8
+    // │   ├── subsomefile 6
+    // ├── evensmallerfile 9
+    // ├── smallerfile 7
+    // └── somefile 2
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(path, &opts);
+    let entries = lrg.sort_descending().get_entries();
+    // subsubdir is the same size as its only file, but comes first by path
+    test_entries_against_filenames(
+        &entries,
+        &vec_of_strings![
+            "testdir",
+            "somefile",
+            "subdir",
+            "subsubdir",
+            "subsubsomefile",
+            "subsomefile",
+            "smallerfile",
+            "subsmallerfile",
+            "evensmallerfile",
+            "link_somefile"
+        ]
+    );
+}
+
+#[test]
+fn test_multiple_keys_order() {
+    // Should count:
+    // testdir/
+    // ├── subdir/
+    // │   ├── subsubdir/
+    // │   │   ├── subsubsomefile 7
+    // │   ├── link_somefile 6
+    // │   ├── subsmallerfile 5
+    // │   ├── subsomefile 4
+    // ├── evensmallerfile 3
+    // ├── smallerfile 2
+    // └── somefile 1
+    let path = Path::new("tests/testdir");
+    let sort = SortBy::Keys(vec![
+        (SortKey::Depth, Direction::Ascending),
+        (SortKey::Size, Direction::Descending),
+    ]);
+    let mut lrg = Lrg::new(path, &LrgOptions::default());
+    test_entries_against_filenames(
+        &lrg.sort_by(&sort).get_entries(),
+        &vec_of_strings![
+            "somefile",
+            "smallerfile",
+            "evensmallerfile",
+            "subsomefile",
+            "subsmallerfile",
+            "link_somefile",
+            "subsubsomefile"
+        ]
+    );
+    // Keeping only the first entries gives the same order
+    let top = Lrg::new_top_n(path, &LrgOptions::default(), &sort, 4);
+    test_entries_against_filenames(
+        &top.get_entries(),
+        &vec_of_strings!["somefile", "smallerfile", "evensmallerfile", "subsomefile"]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unknown_size_order() {
+    use std::fs::{self, Permissions};
+    use std::os::unix::fs::PermissionsExt;
+    // Files in a directory that can be listed but not searched can't be read, so
+    // their size is unknown, and they come last whichever the direction:
+    // unknown_size/
+    // ├── unsearchable/
+    // │   ├── unreadable ?
+    // ├── largerfile 2048
+    // └── emptyfile 0
+    let dir = TempDir::new("unknown_size");
+    fs::create_dir(dir.join("unsearchable")).unwrap();
+    fs::write(dir.join("unsearchable/unreadable"), [0; 1024]).unwrap();
+    fs::write(dir.join("largerfile"), [0; 2048]).unwrap();
+    File::create(dir.join("emptyfile")).unwrap();
+    fs::set_permissions(dir.join("unsearchable"), Permissions::from_mode(0o400)).unwrap();
+    let mut lrg = Lrg::new(&dir, &LrgOptions::default());
+    let sized = Lrg::new(&dir, &LrgOptions { max_size: 1 << 20, ..LrgOptions::default() });
+    let top = Lrg::new_top_n(&dir, &LrgOptions::default(), &SortBy::Ascending, 2);
+    fs::set_permissions(dir.join("unsearchable"), Permissions::from_mode(0o700)).unwrap();
+
+    let entries = lrg.get_entries();
+    let unreadable = entries.iter().find(|entry| entry.file_name() == "unreadable").unwrap();
+    // Root can read it anyway
+    if unreadable.metadata().is_some() {
+        return;
+    }
+    assert_eq!(unreadable.known_size(), None);
+    assert_eq!(unreadable.size(), 0);
+
+    let sorts = [
+        SortBy::Ascending,
+        SortBy::Descending,
+        SortBy::Key(SortKey::Size, Direction::Ascending),
+        SortBy::Key(SortKey::Size, Direction::Descending),
+    ];
+    for sort in &sorts {
+        let entries = lrg.sort_by(sort).get_entries();
+        assert_eq!(entries.last().unwrap().file_name(), "unreadable");
+    }
+    test_entries_against_filenames(&top.get_entries(), &vec_of_strings!["emptyfile", "largerfile"]);
+
+    // It isn't within any sizes, or counted by the stats
+    assert_eq!(sized.get_entries().len(), 2);
+    assert_eq!(lrg.get_stats().files, 2);
+}
@@ -0,0 +1,49 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+use lrg::{Lrg, LrgOptions, OwnerNames};
+use common::TempDir;
+
+#[test]
+fn test_owner_names() {
+    let dir = TempDir::new("owner_names");
+    fs::write(
+        dir.join("passwd"),
+        "# comment\nroot:x:0:0:root:/root:/bin/sh\nalice:x:1000:1000::/home/alice:/bin/sh\n\
+         alias:x:1000:1000::/home/alice:/bin/sh\nbroken:x:notanid:0::/:/bin/sh\n\n",
+    )
+    .unwrap();
+    fs::write(dir.join("group"), "root:x:0:\nstaff:x:50:alice\n").unwrap();
+
+    let names = OwnerNames::from_files(&dir.join("passwd"), &dir.join("group"));
+    assert_eq!(names.user(0), Some("root"));
+    // The first name of an id is used
+    assert_eq!(names.user(1000), Some("alice"));
+    assert_eq!(names.user(50), None);
+    assert_eq!(names.group(50), Some("staff"));
+    assert_eq!(names.group(1000), None);
+}
+
+#[test]
+fn test_owner_names_missing_files() {
+    let names = OwnerNames::from_files(Path::new("tests/does_not_exist"), Path::new("tests/does_not_exist"));
+    assert_eq!(names.user(0), None);
+    assert_eq!(names.group(0), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_group_by_owner() {
+    use std::os::unix::fs::MetadataExt;
+
+    let path = Path::new("tests/testdir");
+    let metadata = fs::metadata(path.join("somefile")).unwrap();
+    let lrg = Lrg::new(path, &LrgOptions::default());
+    let groups = lrg.group_by(|entry| (entry.uid(), entry.gid()));
+    assert_eq!(groups.len(), 1);
+    assert_eq!(*groups[0].key(), (Some(metadata.uid()), Some(metadata.gid())));
+    assert_eq!(groups[0].total(), 1413131);
+}
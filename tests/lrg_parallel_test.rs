@@ -0,0 +1,159 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use lrg::{Lrg, LrgOptions, SortBy};
+use common::TempDir;
+
+// Gets the sorted paths and sizes found with the given options
+fn sorted_results(path: &Path, opts: &LrgOptions) -> Vec<(PathBuf, u64)> {
+    let mut lrg = Lrg::new(path, opts);
+    let entries = lrg.sort_descending().get_entries();
+    entries
+        .iter()
+        .map(|entry| (entry.path().to_path_buf(), entry.size()))
+        .collect()
+}
+
+fn test_parallel_matches_sequential(path: &Path, opts: LrgOptions) {
+    let sequential = Lrg::new(path, &opts);
+    for threads in 2..5 {
+        let parallel_opts = LrgOptions {
+            threads,
+            ..opts.clone()
+        };
+        let parallel = Lrg::new(path, &parallel_opts);
+        // Unsorted entries should come out in the same order
+        let paths = |lrg: &Lrg| {
+            lrg.get_entries()
+                .iter()
+                .map(|entry| (entry.path().to_path_buf(), entry.depth(), entry.size()))
+                .collect::<Vec<(PathBuf, usize, u64)>>()
+        };
+        assert_eq!(paths(&sequential), paths(&parallel));
+        assert_eq!(sequential.get_errors(), parallel.get_errors());
+        assert_eq!(sorted_results(path, &opts), sorted_results(path, &parallel_opts));
+    }
+}
+
+#[test]
+fn test_parallel_basic() {
+    test_parallel_matches_sequential(Path::new("tests/testdir"), LrgOptions::default());
+}
+
+#[test]
+fn test_parallel_dirs() {
+    test_parallel_matches_sequential(
+        Path::new("tests/testdir"),
+        LrgOptions {
+            include_dirs: true,
+            ..LrgOptions::default()
+        },
+    );
+}
+
+#[test]
+fn test_parallel_depths() {
+    test_parallel_matches_sequential(
+        Path::new("tests/testdir"),
+        LrgOptions {
+            min_depth: 2,
+            max_depth: 2,
+            include_dirs: true,
+            ..LrgOptions::default()
+        },
+    );
+}
+
+#[test]
+fn test_parallel_links() {
+    test_parallel_matches_sequential(
+        Path::new("tests/testdir"),
+        LrgOptions {
+            follow_links: true,
+            ..LrgOptions::default()
+        },
+    );
+}
+
+#[test]
+fn test_parallel_file() {
+    test_parallel_matches_sequential(Path::new("tests/testdir/somefile"), LrgOptions::default());
+}
+
+#[test]
+fn test_parallel_top_n() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        threads: 4,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new_top_n(path, &opts, &SortBy::Descending, 3);
+    let sizes: Vec<u64> = lrg.get_entries().iter().map(|entry| entry.size()).collect();
+    assert_eq!(sizes, vec![1024000, 204800, 102400]);
+}
+
+#[test]
+fn test_parallel_removed_subdir() {
+    // A subdirectory removed after the root is listed, but before its group is
+    // walked, has nothing beneath it. Removing it over and over while walking
+    // makes that likely to happen at least once:
+    // removed_subdir/
+    // ├── dir0/ ... dir7/
+    // │   ├── somefile 1024
+    // └── removed/ (removed and recreated while walking)
+    let dir = TempDir::new("removed_subdir");
+    for i in 0..8 {
+        fs::create_dir(dir.join(format!("dir{}", i))).unwrap();
+        fs::write(dir.join(format!("dir{}/somefile", i)), [0; 1024]).unwrap();
+    }
+    let opts = LrgOptions {
+        threads: 4,
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    thread::scope(|scope| {
+        let remover = scope.spawn(|| {
+            for _ in 0..2000 {
+                let _ = fs::create_dir(dir.join("removed"));
+                thread::sleep(Duration::from_micros(50));
+                let _ = fs::remove_dir(dir.join("removed"));
+                thread::sleep(Duration::from_micros(50));
+            }
+        });
+        while !remover.is_finished() {
+            let lrg = Lrg::new(&dir, &opts);
+            let files = lrg
+                .get_entries()
+                .iter()
+                .filter(|entry| !entry.file_type().is_dir())
+                .count();
+            assert_eq!(files, 8);
+        }
+    });
+}
+
+#[test]
+fn test_parallel_many_files() {
+    // Subdirectories with more entries than are sent to the main thread at once
+    let dir = TempDir::new("many_files");
+    for i in 0..3 {
+        let subdir = dir.join(format!("dir{}", i));
+        fs::create_dir_all(subdir.join("inner")).unwrap();
+        for j in 0..1500 {
+            fs::write(subdir.join(format!("file{}", j)), vec![0; j % 7]).unwrap();
+            fs::write(subdir.join("inner").join(format!("file{}", j)), vec![0; j % 5]).unwrap();
+        }
+    }
+    test_parallel_matches_sequential(
+        &dir,
+        LrgOptions {
+            include_dirs: true,
+            ..LrgOptions::default()
+        },
+    );
+}
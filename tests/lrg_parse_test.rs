@@ -0,0 +1,118 @@
+extern crate lrg;
+
+use std::time::Duration;
+use lrg::{parse_duration, parse_size};
+
+#[test]
+fn test_parse_size_units() {
+    assert_eq!(parse_size("0", false), Some(0));
+    assert_eq!(parse_size("100", false), Some(100));
+    assert_eq!(parse_size("100B", false), Some(100));
+    assert_eq!(parse_size("512k", false), Some(512 << 10));
+    assert_eq!(parse_size("512K", false), Some(512 << 10));
+    assert_eq!(parse_size("512KB", false), Some(512 << 10));
+    assert_eq!(parse_size("10M", false), Some(10 << 20));
+    assert_eq!(parse_size("10mb", false), Some(10 << 20));
+    assert_eq!(parse_size("2G", false), Some(2 << 30));
+    assert_eq!(parse_size("3T", false), Some(3 << 40));
+    assert_eq!(parse_size("4P", false), Some(4 << 50));
+    assert_eq!(parse_size("5E", false), Some(5 << 60));
+    // Whitespace between the number and unit, and around them
+    assert_eq!(parse_size(" 10 M ", false), Some(10 << 20));
+}
+
+#[test]
+fn test_parse_size_binary_units() {
+    // Units ending in iB are binary, even with decimal units
+    for &decimal in &[false, true] {
+        assert_eq!(parse_size("1KiB", decimal), Some(1024));
+        assert_eq!(parse_size("1kib", decimal), Some(1024));
+        assert_eq!(parse_size("1Ki", decimal), Some(1024));
+        assert_eq!(parse_size("10MiB", decimal), Some(10 << 20));
+        assert_eq!(parse_size("2GiB", decimal), Some(2 << 30));
+    }
+    // Bytes can't be binary
+    assert_eq!(parse_size("100iB", false), None);
+    assert_eq!(parse_size("100i", false), None);
+}
+
+#[test]
+fn test_parse_size_decimal() {
+    assert_eq!(parse_size("100", true), Some(100));
+    assert_eq!(parse_size("100B", true), Some(100));
+    assert_eq!(parse_size("512k", true), Some(512_000));
+    assert_eq!(parse_size("512KB", true), Some(512_000));
+    assert_eq!(parse_size("10M", true), Some(10_000_000));
+    assert_eq!(parse_size("2GB", true), Some(2_000_000_000));
+    assert_eq!(parse_size("1E", true), Some(1_000_000_000_000_000_000));
+}
+
+#[test]
+fn test_parse_size_fractions() {
+    assert_eq!(parse_size("1.5GiB", false), Some(3 << 29));
+    assert_eq!(parse_size("1.5K", false), Some(1536));
+    assert_eq!(parse_size("1.5K", true), Some(1500));
+    assert_eq!(parse_size("0.5M", true), Some(500_000));
+    assert_eq!(parse_size(".5K", false), Some(512));
+    assert_eq!(parse_size("2.", false), Some(2));
+    // Fractions of a byte are rounded
+    assert_eq!(parse_size("1.0001K", false), Some(1024));
+    assert_eq!(parse_size("2.5", false), Some(3));
+}
+
+#[test]
+fn test_parse_size_overflow() {
+    assert_eq!(parse_size("15EiB", false), Some(15 << 60));
+    assert_eq!(parse_size("18446744073709549568", false), Some(18446744073709549568));
+    // 2^64 and larger don't fit
+    assert_eq!(parse_size("16EiB", false), None);
+    assert_eq!(parse_size("18446744073709551616", false), None);
+    assert_eq!(parse_size("19E", true), None);
+    assert_eq!(parse_size("1000000E", false), None);
+}
+
+#[test]
+fn test_parse_size_invalid() {
+    for size in &["", " ", "K", "KB", ".", "1.2.3", "-1", "-1K", "+1K", "1e3", "10X", "10KK", "10 K B", "ten", "10Mb5", "NaN", "inf"] {
+        assert_eq!(parse_size(size, false), None, "parsed {:?}", size);
+        assert_eq!(parse_size(size, true), None, "parsed {:?}", size);
+    }
+}
+
+#[test]
+fn test_parse_duration_units() {
+    let minute = 60;
+    let hour = 60 * minute;
+    let day = 24 * hour;
+    assert_eq!(parse_duration("0"), Some(Duration::from_secs(0)));
+    assert_eq!(parse_duration("45"), Some(Duration::from_secs(45)));
+    assert_eq!(parse_duration("45s"), Some(Duration::from_secs(45)));
+    assert_eq!(parse_duration("30m"), Some(Duration::from_secs(30 * minute)));
+    assert_eq!(parse_duration("12h"), Some(Duration::from_secs(12 * hour)));
+    assert_eq!(parse_duration("90d"), Some(Duration::from_secs(90 * day)));
+    assert_eq!(parse_duration("2w"), Some(Duration::from_secs(14 * day)));
+    assert_eq!(parse_duration("1y"), Some(Duration::from_secs(365 * day)));
+    assert_eq!(parse_duration(" 2 h "), Some(Duration::from_secs(2 * hour)));
+}
+
+#[test]
+fn test_parse_duration_fractions() {
+    assert_eq!(parse_duration("1.5h"), Some(Duration::from_secs(90 * 60)));
+    assert_eq!(parse_duration(".5m"), Some(Duration::from_secs(30)));
+    assert_eq!(parse_duration("0.25s"), Some(Duration::from_millis(250)));
+}
+
+#[test]
+fn test_parse_duration_overflow() {
+    assert!(parse_duration("1000000000y").is_some());
+    assert_eq!(parse_duration("1000000000000y"), None);
+    assert_eq!(parse_duration("99999999999999999999999"), None);
+}
+
+#[test]
+fn test_parse_duration_invalid() {
+    // Units are case sensitive, since M could mean months
+    for duration in &["", " ", "d", ".", "1.2.3", "-1d", "+1d", "1e3", "10D", "10M", "10x", "10 d s", "ninety", "NaN"] {
+        assert_eq!(parse_duration(duration), None, "parsed {:?}", duration);
+    }
+}
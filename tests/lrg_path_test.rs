@@ -0,0 +1,41 @@
+extern crate lrg;
+
+use std::env;
+use std::path::Path;
+use lrg::absolute_path;
+
+#[test]
+fn test_absolute_path() {
+    let cwd = env::current_dir().unwrap();
+    assert_eq!(absolute_path(Path::new("tests/testdir")), cwd.join("tests/testdir"));
+    assert_eq!(absolute_path(Path::new(".")), cwd);
+    assert_eq!(absolute_path(Path::new("")), cwd);
+    assert_eq!(absolute_path(Path::new("/tmp")), Path::new("/tmp"));
+}
+
+#[test]
+fn test_absolute_path_normalized() {
+    let cwd = env::current_dir().unwrap();
+    assert_eq!(absolute_path(Path::new("tests/testdir/./somefile")), cwd.join("tests/testdir/somefile"));
+    assert_eq!(
+        absolute_path(Path::new("tests/testdir/../testdir/subdir/subsomefile")),
+        cwd.join("tests/testdir/subdir/subsomefile")
+    );
+    assert_eq!(absolute_path(Path::new("./tests/./testdir/subdir/..")), cwd.join("tests/testdir"));
+    // Paths that don't exist are normalized too
+    assert_eq!(absolute_path(Path::new("/missing/dir/../file")), Path::new("/missing/file"));
+    // The parent of the root is the root
+    assert_eq!(absolute_path(Path::new("/../tmp")), Path::new("/tmp"));
+    assert_eq!(absolute_path(Path::new("/tmp/../..")), Path::new("/"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_absolute_path_keeps_parent_of_links() {
+    // The parent of a link is the parent of where it points to, so isn't removed
+    let cwd = env::current_dir().unwrap();
+    let link = cwd.join("tests/testdir/subdir/link_somefile");
+    assert!(link.symlink_metadata().unwrap().file_type().is_symlink());
+    assert_eq!(absolute_path(&link.join("..")), link.join(".."));
+    assert_eq!(absolute_path(&link.join("../..")), link.join("../.."));
+}
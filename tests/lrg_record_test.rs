@@ -0,0 +1,49 @@
+extern crate lrg;
+extern crate serde_json;
+
+use std::path::Path;
+use lrg::{EntryType, Lrg, LrgOptions, LrgRecord};
+
+#[test]
+fn test_records_match_entries() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    for entry in lrg.get_entries() {
+        let record = LrgRecord::from(&entry);
+        assert_eq!(record.path, entry.path());
+        assert_eq!(record.size, entry.known_size());
+        assert!(record.modified.is_some());
+        let file_type = if entry.file_type().is_dir() {
+            EntryType::Dir
+        } else if entry.file_type().is_symlink() {
+            EntryType::Symlink
+        } else {
+            EntryType::File
+        };
+        assert_eq!(record.file_type, file_type);
+    }
+}
+
+#[test]
+fn test_record_json() {
+    let path = Path::new("tests/testdir/somefile");
+    let lrg = Lrg::new(path, &LrgOptions::default());
+    let record = LrgRecord::from(&lrg.get_entries()[0]);
+    let json = serde_json::to_value(&record).unwrap();
+    assert_eq!(json["path"], "tests/testdir/somefile");
+    assert_eq!(json["size"], 1024000);
+    assert_eq!(json["file_type"], "file");
+    assert_eq!(serde_json::from_value::<LrgRecord>(json).unwrap(), record);
+}
+
+#[test]
+fn test_entry_type_names() {
+    // Printed names match the serialized ones
+    for file_type in &[EntryType::File, EntryType::Dir, EntryType::Symlink, EntryType::Other] {
+        assert_eq!(serde_json::to_value(file_type).unwrap(), file_type.to_string());
+    }
+}
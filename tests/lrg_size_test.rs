@@ -1,14 +1,19 @@
 extern crate lrg;
 
+mod common;
+
+use std::fs::{self, File};
 use std::path::Path;
-use lrg::{Lrg, LrgOptions, DirEntry};
+use lrg::{Lrg, LrgOptions, LrgEntry, SizeMetric};
+use common::TempDir;
 
 
-fn test_entries_against_sizes(entries: &Vec<DirEntry>, sizes: &Vec<u64>) {
+#[allow(clippy::ptr_arg)]
+fn test_entries_against_sizes(entries: &Vec<LrgEntry>, sizes: &Vec<u64>) {
     assert_eq!(entries.len(), sizes.len());
     for (i, entry) in entries.iter().enumerate() {
-        // println!("{:?}", entry.metadata().expect("Cannot get filesize").len());
-        assert_eq!(entry.metadata().expect("Cannot get filesize").len(), sizes[i]);
+        // println!("{:?}", entry.size());
+        assert_eq!(entry.size(), sizes[i]);
     }
 }
 
@@ -100,16 +105,16 @@ fn test_basic_max_depth_file_size() {
 #[test]
 fn test_basic_file_and_dir_size() {
     // Should count:
-    // testdir/ 7
-    // ├── subdir/ 8
-    // │   ├── subsubdir/ 9
-    // │   │   ├── subsubsomefile 2
+    // testdir/ 1
+    // ├── subdir/ 3
+    // │   ├── subsubdir/ 4
+    // │   │   ├── subsubsomefile 4
     // │   ├── link_somefile 10
-    // │   ├── subsmallerfile 5
-    // │   ├── subsomefile 3
-    // ├── evensmallerfile 6
-    // ├── smallerfile 4
-    // └── somefile 1
+    // │   ├── subsmallerfile 8
+    // │   ├── subsomefile 6
+    // ├── evensmallerfile 9
+    // ├── smallerfile 7
+    // └── somefile 2
     let path = Path::new("tests/testdir");
     let opts = LrgOptions {
         include_dirs: true,
@@ -120,20 +125,70 @@ fn test_basic_file_and_dir_size() {
     test_entries_against_sizes(
         &entries,
         &vec![
+            1413131,
             1024000,
+            327691,
+            204800,
             204800,
             102400,
             51200,
             20480,
             10240,
-            4096,
-            4096,
-            4096,
             11,
         ]
     );
 }
 
+#[test]
+fn test_subdir_file_and_dir_size() {
+    // Should count:
+    // subdir/ 1
+    // ├── subsubdir/ 2
+    // │   ├── subsubsomefile 2
+    // ├── link_somefile 6
+    // ├── subsmallerfile 5
+    // └── subsomefile 4
+    let path = Path::new("tests/testdir/subdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(path, &opts);
+    let entries = lrg.sort_descending().get_entries();
+    test_entries_against_sizes(
+        &entries,
+        &vec![
+            327691,
+            204800,
+            204800,
+            102400,
+            20480,
+            11,
+        ]
+    );
+}
+
+#[test]
+fn test_subdir_dir_max_depth_size() {
+    // Should count:
+    // subdir/ 1
+    // ├── subsubdir/ 2
+    // │   ├── subsubsomefile
+    // ├── link_somefile
+    // ├── subsmallerfile
+    // └── subsomefile
+    let path = Path::new("tests/testdir/subdir");
+    let opts = LrgOptions {
+        max_depth: 1,
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(path, &opts);
+    let entries = lrg.sort_descending().get_entries();
+    // Directories still count files below the maximum depth
+    test_entries_against_sizes(&entries[..2].to_vec(), &vec![327691, 204800]);
+}
+
 #[test]
 fn test_basic_link_size() {
     // Should count:
@@ -197,4 +252,51 @@ fn test_min_depth_size() {
             11,
         ]
     )
-}
\ No newline at end of file
+}
+#[test]
+fn test_metadata_matches_size() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    for entry in lrg.get_entries() {
+        let meta = entry.metadata().expect("Cannot get metadata");
+        if meta.is_dir() {
+            assert!(entry.size() >= 204800);
+        } else {
+            assert_eq!(meta.len(), entry.size());
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_allocated_sizes() {
+    // Should count:
+    // sparsedir/ 1
+    // ├── sparsefile 3
+    // └── somefile 2
+    let dir = TempDir::new("sparsedir");
+    File::create(dir.join("sparsefile")).unwrap().set_len(1 << 30).unwrap();
+    fs::copy("tests/testdir/somefile", dir.join("somefile")).unwrap();
+
+    let opts = LrgOptions {
+        include_dirs: true,
+        size_metric: SizeMetric::Allocated,
+        ..LrgOptions::default()
+    };
+    let mut lrg = Lrg::new(&dir, &opts);
+    let entries = lrg.sort_descending().get_entries();
+
+    assert_eq!(entries.len(), 3);
+    // The sparse file barely uses any blocks, the copied file uses at least its size
+    assert_eq!(entries[2].file_name(), "sparsefile");
+    assert!(entries[2].size() < 1024000);
+    assert!(entries[0].size() >= 1024000);
+    assert_eq!(entries[0].size(), entries[1].size() + entries[2].size());
+    for entry in &entries {
+        assert_eq!(entry.size() % 512, 0);
+    }
+}
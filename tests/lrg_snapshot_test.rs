@@ -0,0 +1,113 @@
+extern crate lrg;
+
+mod common;
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use lrg::{ChangeKind, EntryType, Lrg, LrgOptions, Snapshot};
+use common::TempDir;
+
+// Creates a directory to scan in the temp directory:
+// scandir/
+// ├── sub/
+// │   ├── grows 1024
+// │   ├── same 512
+// ├── removed 256
+// └── shrinks 2048
+fn create_scandir(name: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::write(dir.join("sub/grows"), [0; 1024]).unwrap();
+    fs::write(dir.join("sub/same"), [0; 512]).unwrap();
+    fs::write(dir.join("removed"), [0; 256]).unwrap();
+    fs::write(dir.join("shrinks"), [0; 2048]).unwrap();
+    dir
+}
+
+// Scans the directory, with directories included
+fn scan(dir: &Path) -> Snapshot {
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    Lrg::new(dir, &opts).snapshot(dir)
+}
+
+#[test]
+fn test_snapshot_paths() {
+    let dir = create_scandir("snapshot_paths");
+    let snapshot = scan(&dir);
+    assert_eq!(snapshot.root(), &*dir);
+    let mut paths: Vec<PathBuf> = snapshot.entries().iter().map(|record| record.path.clone()).collect();
+    paths.sort();
+    let expected: Vec<PathBuf> = ["", "removed", "shrinks", "sub", "sub/grows", "sub/same"]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(paths, expected);
+    // The root is normalized, unlike the entries' paths
+    assert_eq!(scan(&dir.join("./sub/..")).root(), &*dir);
+}
+
+#[test]
+fn test_snapshot_save_load() {
+    let dir = create_scandir("snapshot_save_load");
+    let snapshot = scan(&dir);
+    let file = dir.join("scan.lrg");
+    snapshot.save(&file).unwrap();
+    assert_eq!(Snapshot::load(&file).unwrap(), snapshot);
+}
+
+#[test]
+fn test_snapshot_load_invalid() {
+    let dir = create_scandir("snapshot_load_invalid");
+    let file = dir.join("scan.lrg");
+
+    fs::write(&file, "not json").unwrap();
+    assert_eq!(Snapshot::load(&file).unwrap_err().kind(), ErrorKind::InvalidData);
+    fs::write(&file, r#"{"format": "something-else", "version": 1}"#).unwrap();
+    assert_eq!(Snapshot::load(&file).unwrap_err().kind(), ErrorKind::InvalidData);
+    // Newer versions can't be read
+    fs::write(&file, r#"{"format": "lrg-scan", "version": 2}"#).unwrap();
+    assert_eq!(Snapshot::load(&file).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert_eq!(Snapshot::load(&dir.join("missing.lrg")).unwrap_err().kind(), ErrorKind::NotFound);
+}
+
+#[test]
+fn test_snapshot_diff() {
+    let dir = create_scandir("snapshot_diff");
+    let old = scan(&dir);
+
+    fs::write(dir.join("sub/grows"), [0; 4096]).unwrap();
+    fs::remove_file(dir.join("removed")).unwrap();
+    fs::write(dir.join("shrinks"), [0; 1024]).unwrap();
+    fs::write(dir.join("sub/added"), [0; 128]).unwrap();
+    let new = scan(&dir);
+
+    let changes: Vec<(PathBuf, ChangeKind, i64)> = old
+        .diff(&new)
+        .iter()
+        .map(|change| (change.path().to_path_buf(), change.kind(), change.delta()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (PathBuf::from("sub"), ChangeKind::Grown, 3072 + 128),
+            (PathBuf::from("sub/grows"), ChangeKind::Grown, 3072),
+            (PathBuf::from(""), ChangeKind::Grown, 3072 + 128 - 1024 - 256),
+            (PathBuf::from("shrinks"), ChangeKind::Shrunk, -1024),
+            (PathBuf::from("removed"), ChangeKind::Removed, -256),
+            (PathBuf::from("sub/added"), ChangeKind::Added, 128),
+        ]
+    );
+
+    let diff = old.diff(&new);
+    let sub = diff.iter().find(|change| change.path() == Path::new("sub")).unwrap();
+    assert_eq!(sub.file_type(), EntryType::Dir);
+    assert_eq!(sub.old_size(), Some(1024 + 512));
+    assert_eq!(sub.new_size(), Some(4096 + 512 + 128));
+    let removed = diff.iter().find(|change| change.kind() == ChangeKind::Removed).unwrap();
+    assert_eq!(removed.new_size(), None);
+    assert!(new.diff(&new).is_empty());
+}
@@ -0,0 +1,99 @@
+extern crate lrg;
+
+mod common;
+
+use std::path::Path;
+use lrg::{Lrg, LrgOptions, LrgStats, SortBy};
+use common::TempDir;
+
+// Should count:
+// testdir/
+// ├── subdir/
+// │   ├── subsubdir/
+// │   │   ├── subsubsomefile 204800
+// │   ├── link_somefile 11
+// │   ├── subsmallerfile 20480
+// │   ├── subsomefile 102400
+// ├── evensmallerfile 10240
+// ├── smallerfile 51200
+// └── somefile 1024000
+fn testdir_stats() -> LrgStats {
+    LrgStats {
+        files: 7,
+        total_bytes: 1413131,
+        mean: 1413131.0 / 7.0,
+        median: 51200,
+        p90: 1024000,
+        p99: 1024000,
+        largest: 1024000,
+        smallest: 11,
+        errors: 0,
+    }
+}
+
+#[test]
+fn test_stats() {
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new(path, &LrgOptions::default());
+    assert_eq!(lrg.get_stats(), testdir_stats());
+}
+
+#[test]
+fn test_stats_top_n_kept() {
+    // Without asking for stats, only the files kept are counted
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new_top_n(path, &LrgOptions::default(), &SortBy::Descending, 2);
+    let stats = lrg.get_stats();
+    assert_eq!(stats.files, 2);
+    assert_eq!(stats.total_bytes, 1024000 + 204800);
+}
+
+#[test]
+fn test_stats_top_n() {
+    // Stats count every file found, not only the ones kept
+    let path = Path::new("tests/testdir");
+    let lrg = Lrg::new_top_n_with_stats(path, &LrgOptions::default(), &SortBy::Descending, 2);
+    assert_eq!(lrg.get_entries().len(), 2);
+    assert_eq!(lrg.get_stats(), testdir_stats());
+}
+
+#[test]
+fn test_stats_skip_dirs() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let lrg = Lrg::new(path, &opts);
+    assert_eq!(lrg.get_stats(), testdir_stats());
+}
+
+#[test]
+fn test_stats_filtered() {
+    // Only counts the files listed
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        min_size: 50000,
+        ..LrgOptions::default()
+    };
+    let stats = Lrg::new(path, &opts).get_stats();
+    assert_eq!(stats.files, 4);
+    assert_eq!(stats.total_bytes, 1024000 + 204800 + 102400 + 51200);
+    assert_eq!(stats.median, 102400);
+    assert_eq!(stats.smallest, 51200);
+}
+
+#[test]
+fn test_stats_empty() {
+    let dir = TempDir::new("stats_empty");
+    let stats = Lrg::new(&dir, &LrgOptions::default()).get_stats();
+    assert_eq!(stats, LrgStats::default());
+}
+
+#[test]
+fn test_stats_errors() {
+    let path = Path::new("tests/does_not_exist");
+    let stats = Lrg::new(path, &LrgOptions::default()).get_stats();
+    assert_eq!(stats.files, 0);
+    assert_eq!(stats.errors, 1);
+}
@@ -0,0 +1,114 @@
+extern crate lrg;
+
+use std::path::Path;
+use lrg::{Lrg, LrgOptions, TreeNode};
+
+// Gets the sizes and names of the node's children
+fn children(node: &TreeNode) -> Vec<(u64, String)> {
+    node.children()
+        .iter()
+        .map(|child| (child.size(), child.name().to_string_lossy().into_owned()))
+        .collect()
+}
+
+// Should arrange:
+// testdir/ 1413131
+// ├── subdir/ 327691
+// │   ├── subsubdir/ 204800
+// │   │   ├── subsubsomefile 204800
+// │   ├── link_somefile 11
+// │   ├── subsmallerfile 20480
+// │   ├── subsomefile 102400
+// ├── evensmallerfile 10240
+// ├── smallerfile 51200
+// └── somefile 1024000
+#[test]
+fn test_tree_with_dirs() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let tree = Lrg::new(path, &opts).tree(path);
+    assert_eq!(tree.path(), path);
+    assert_eq!(tree.size(), 1413131);
+    assert!(tree.is_dir());
+    assert!(tree.entry().is_some());
+    assert_eq!(
+        children(&tree),
+        vec![
+            (1024000, "somefile".to_string()),
+            (327691, "subdir".to_string()),
+            (51200, "smallerfile".to_string()),
+            (10240, "evensmallerfile".to_string()),
+        ]
+    );
+
+    let subdir = tree.find(&path.join("subdir")).unwrap();
+    assert!(subdir.is_dir());
+    assert_eq!(
+        children(subdir),
+        vec![
+            (204800, "subsubdir".to_string()),
+            (102400, "subsomefile".to_string()),
+            (20480, "subsmallerfile".to_string()),
+            (11, "link_somefile".to_string()),
+        ]
+    );
+    assert!(!subdir.children()[1].is_dir());
+}
+
+#[test]
+fn test_tree_without_dirs() {
+    // Directories are sized by the files listed beneath them
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        min_size: 50000,
+        ..LrgOptions::default()
+    };
+    let tree = Lrg::new(path, &opts).tree(path);
+    assert!(tree.entry().is_none());
+    assert_eq!(tree.size(), 1024000 + 204800 + 102400 + 51200);
+    let subdir = tree.find(&path.join("subdir")).unwrap();
+    assert!(subdir.entry().is_none());
+    assert!(subdir.is_dir());
+    assert_eq!(subdir.size(), 204800 + 102400);
+    assert!(tree.find(&path.join("subdir/subsmallerfile")).is_none());
+}
+
+#[test]
+fn test_tree_remove() {
+    let path = Path::new("tests/testdir");
+    let opts = LrgOptions {
+        include_dirs: true,
+        ..LrgOptions::default()
+    };
+    let mut tree = Lrg::new(path, &opts).tree(path);
+    let removed = tree.remove(&path.join("subdir/subsubdir")).unwrap();
+    assert_eq!(removed.size(), 204800);
+    assert_eq!(tree.size(), 1413131 - 204800);
+    assert_eq!(tree.find(&path.join("subdir")).unwrap().size(), 327691 - 204800);
+    assert!(tree.find(&path.join("subdir/subsubdir/subsubsomefile")).is_none());
+    // Nodes that aren't in the tree can't be removed
+    assert!(tree.remove(&path.join("subdir/subsubdir")).is_none());
+    assert!(tree.remove(path).is_none());
+    assert_eq!(tree.size(), 1413131 - 204800);
+}
+
+#[test]
+fn test_tree_from_entries() {
+    // Only the largest files, in directories sized by them
+    let path = Path::new("tests/testdir");
+    let mut lrg = Lrg::new(path, &LrgOptions::default());
+    let entries: Vec<_> = lrg.sort_descending().get_entries().into_iter().take(2).collect();
+    let tree = TreeNode::new(path, &entries);
+    assert_eq!(tree.size(), 1024000 + 204800);
+    assert_eq!(
+        children(&tree),
+        vec![(1024000, "somefile".to_string()), (204800, "subdir".to_string())]
+    );
+    let subsubdir = tree.find(&path.join("subdir/subsubdir")).unwrap();
+    assert_eq!(children(subsubdir), vec![(204800, "subsubsomefile".to_string())]);
+    // Entries outside of the root are skipped
+    assert!(TreeNode::new(Path::new("src"), &entries).children().is_empty());
+}